@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::checks::values::ValueErrorKind;
+use crate::diagnostic::Diagnostic;
+use crate::lint::Report;
+
+/// Identifies a diagnostic independently of line numbers, so moving a key does not change it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// The ID of the rule, one of `RULES`
+    pub rule: String,
+    /// Distinguishes the kinds of violations of a rule, the name of the kind of a style violation or the regex
+    /// of a marker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub file: PathBuf,
+    /// The key path, empty for diagnostics concerning an entire file
+    pub key: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    count: usize,
+}
+
+/// The accepted diagnostics and how often each fingerprint occurred.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    entries: BTreeMap<Fingerprint, usize>,
+}
+
+impl Fingerprint {
+    pub fn of(diagnostic: &Diagnostic) -> Fingerprint {
+        let (kind, file, key) = match diagnostic {
+            Diagnostic::NotFound { file, folder } => (None, folder.join(file), Vec::new()),
+            Diagnostic::Parse { file, .. } => (None, file.clone(), Vec::new()),
+            Diagnostic::Style { kind, key, location } => (Some(kind.name().to_string()), location.file.clone(), key.clone()),
+            Diagnostic::MissingKey { file, key } => (None, file.clone(), key.clone()),
+            Diagnostic::TypeMismatch { key, right, .. } => (None, right.file.clone(), key.clone()),
+            Diagnostic::UnusedKey { file, key } => (None, file.clone(), key.clone()),
+            Diagnostic::UndefinedKey { file, key, .. } => (None, file.clone(), key.clone()),
+            Diagnostic::Untranslated { key, location, .. } => (None, location.file.clone(), key.clone()),
+            Diagnostic::Value { kind, key, location } => match kind {
+                ValueErrorKind::Marker(regex) => (Some(regex.clone()), location.file.clone(), key.clone()),
+                _ => (None, location.file.clone(), key.clone()),
+            },
+            Diagnostic::Length { key, location, .. } => (None, location.file.clone(), key.clone()),
+            Diagnostic::InvalidDirective { key, location, .. } => (None, location.file.clone(), key.clone()),
+        };
+
+        Fingerprint {
+            rule: diagnostic.rule().to_string(),
+            kind,
+            file,
+            key,
+        }
+    }
+}
+
+impl Baseline {
+    /// Records every diagnostic of the report.
+    pub fn new(report: &Report) -> Baseline {
+        let mut entries = BTreeMap::new();
+        for diagnostic in report.diagnostics() {
+            *entries.entry(Fingerprint::of(diagnostic)).or_default() += 1;
+        }
+
+        Baseline { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let entries = serde_json::from_str::<Vec<Entry>>(&content).map_err(|e| e.to_string())?;
+
+        Ok(Baseline {
+            entries: entries.into_iter().map(|entry| (entry.fingerprint, entry.count)).collect(),
+        })
+    }
+
+    /// Stores the baseline as a sorted JSON array, so changes of it are easy to review.
+    pub fn store(&self, path: &Path) -> std::io::Result<()> {
+        let entries = self.entries.iter()
+            .map(|(fingerprint, count)| Entry {
+                fingerprint: fingerprint.clone(),
+                count: *count,
+            })
+            .collect::<Vec<_>>();
+        fs::write(path, serde_json::to_string_pretty(&entries)? + "\n")
+    }
+
+    /// Removes the diagnostics recorded in the baseline from the report, a fingerprint recorded `n` times
+    /// suppresses at most `n` diagnostics. Returns the entries which did not occur anymore and how often.
+    pub fn apply(&self, report: &mut Report) -> Vec<(Fingerprint, usize)> {
+        let mut remaining = self.entries.clone();
+        let mut keep = |diagnostic: &Diagnostic| match remaining.get_mut(&Fingerprint::of(diagnostic)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        };
+        report.diagnostics.retain(&mut keep);
+        for group in &mut report.groups {
+            group.diagnostics.retain(&mut keep);
+        }
+        report.count_untranslated();
+
+        remaining.into_iter().filter(|(_, count)| *count > 0).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::checks::file_style::file_style;
+    use crate::checks::suppress::Suppressions;
+    use crate::diagnostic::Location;
+    use crate::io::LoadedFile;
+    use crate::lint::GroupReport;
+    use crate::{JsonStyle, LineEnding, Parser, SortAlgorithm, SortOrder};
+    use super::*;
+
+    fn missing(file: &str, key: &str) -> Diagnostic {
+        Diagnostic::MissingKey {
+            file: PathBuf::from(file),
+            key: vec![key.to_string()],
+        }
+    }
+
+    fn report(diagnostics: Vec<Diagnostic>) -> Report {
+        Report {
+            diagnostics: Vec::new(),
+            groups: vec![GroupReport {
+                path: PathBuf::from("common.json"),
+                files: Vec::new(),
+                diagnostics,
+            }],
+            untranslated: Vec::new(),
+            severities: Default::default(),
+            cache_error: None,
+        }
+    }
+
+    #[test]
+    fn only_new_diagnostics_remain() {
+        let untranslated = |line| Diagnostic::Untranslated {
+            key: vec!["a".to_string()],
+            location: Location {
+                file: PathBuf::from("de/common.json"),
+                start_line: line,
+                end_line: line,
+                column: 7,
+            },
+            reference: PathBuf::from("en/common.json"),
+        };
+        let baseline = Baseline::new(&report(vec![
+            missing("fr/common.json", "a"),
+            missing("fr/common.json", "b"),
+            untranslated(2),
+        ]));
+        assert_eq!(baseline.len(), 3);
+
+        // The untranslated value moved to another line
+        let mut current = report(vec![missing("fr/common.json", "a"), missing("fr/common.json", "c"), untranslated(5)]);
+        let removable = baseline.apply(&mut current);
+
+        assert_eq!(current.diagnostics().collect::<Vec<_>>(), vec![&missing("fr/common.json", "c")]);
+        assert_eq!(removable, vec![(Fingerprint::of(&missing("fr/common.json", "b")), 1)]);
+    }
+
+    #[test]
+    fn baselined_values_are_not_counted_as_untranslated() {
+        let untranslated = |key: &str| Diagnostic::Untranslated {
+            key: vec![key.to_string()],
+            location: Location {
+                file: PathBuf::from("de/common.json"),
+                start_line: 1,
+                end_line: 1,
+                column: 7,
+            },
+            reference: PathBuf::from("en/common.json"),
+        };
+        let baseline = Baseline::new(&report(vec![untranslated("a")]));
+
+        let mut current = report(vec![untranslated("a"), untranslated("b")]);
+        current.untranslated = vec![(PathBuf::from("de"), 2)];
+        baseline.apply(&mut current);
+        assert_eq!(current.untranslated, vec![(PathBuf::from("de"), 1)]);
+    }
+
+    #[test]
+    fn count_repeated_fingerprints() {
+        let style = |line| Diagnostic::Style {
+            kind: crate::parser::error::StyleErrorKind::Sorting,
+            key: vec!["a".to_string()],
+            location: Location {
+                file: PathBuf::from("en/common.json"),
+                start_line: line,
+                end_line: line,
+                column: 5,
+            },
+        };
+        let baseline = Baseline::new(&report(vec![style(2)]));
+
+        let mut current = report(vec![style(3), style(4)]);
+        assert!(baseline.apply(&mut current).is_empty());
+        assert_eq!(current.diagnostics().count(), 1);
+    }
+
+    #[test]
+    fn new_style_errors_in_the_same_file_remain() {
+        let style_errors = |content: &str| {
+            let parser = Parser::new(JsonStyle::STYLED {
+                line_endings: LineEnding::IGNORE,
+                indentation: None,
+                post_colon: None,
+                sort_algorithm: SortAlgorithm::NORMAL,
+                key_order: None,
+                order: SortOrder::ASC,
+            });
+            let file = LoadedFile::new(PathBuf::from("en/common.json"), content.to_string());
+            let mut errors = Vec::new();
+            file_style(&parser, &file, &Suppressions::default(), &mut errors);
+            errors
+        };
+        let baseline = Baseline::new(&report(style_errors("{\"b\": \"\", \"a\": \"\"}")));
+
+        // The recorded error is fixed and another one of the same kind is introduced
+        let mut current = report(style_errors("{\"a\": \"\", \"b\": \"\", \"d\": \"\", \"c\": \"\"}"));
+        assert_eq!(baseline.apply(&mut current).len(), 1);
+        assert_eq!(current.diagnostics().map(Fingerprint::of).map(|f| f.key).collect::<Vec<_>>(), vec![vec!["c".to_string()]]);
+    }
+
+    #[test]
+    fn fingerprints_name_the_rule_and_the_kind() {
+        let style = Fingerprint::of(&Diagnostic::Style {
+            kind: crate::parser::error::StyleErrorKind::PostColonTooMuch,
+            key: vec!["a".to_string()],
+            location: Location {
+                file: PathBuf::from("en/common.json"),
+                start_line: 2,
+                end_line: 2,
+                column: 5,
+            },
+        });
+        let not_found = Fingerprint::of(&Diagnostic::NotFound {
+            file: PathBuf::from("common.json"),
+            folder: PathBuf::from("fr"),
+        });
+
+        assert_eq!((style.rule.as_str(), style.kind.as_deref()), ("post-colon", Some("post-colon-too-much")));
+        assert_eq!((not_found.rule.as_str(), not_found.kind), ("missing-file", None));
+        assert_eq!(
+            serde_json::to_string(&Fingerprint::of(&missing("fr/common.json", "a"))).unwrap(),
+            "{\"rule\":\"missing-key\",\"file\":\"fr/common.json\",\"key\":[\"a\"]}",
+        );
+    }
+
+    #[test]
+    fn store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+        let baseline = Baseline::new(&report(vec![missing("fr/common.json", "a"), missing("fr/common.json", "a")]));
+        baseline.store(&path).unwrap();
+
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+        assert!(fs::read_to_string(&path).unwrap().contains("\"count\": 2"));
+        assert!(Baseline::load(&dir.path().join("missing.json")).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::diagnostic::Diagnostic;
+
+const CACHE_FILE: &str = "cache.json";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// The hash of the file content and the style it was checked with
+    pub key: u64,
+    pub parsed: bool,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GroupEntry {
+    pub members: Vec<(PathBuf, u64)>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Cache {
+    version: String,
+    files: HashMap<PathBuf, FileEntry>,
+    groups: HashMap<PathBuf, GroupEntry>,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Cache {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            files: HashMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl Cache {
+    /// Loads the cache from `dir`, a missing, unreadable or outdated cache yields an empty one.
+    pub fn load(dir: &Path) -> Cache {
+        fs::read_to_string(dir.join(CACHE_FILE)).ok()
+            .and_then(|content| serde_json::from_str::<Cache>(&content).ok())
+            .filter(|cache| cache.version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default()
+    }
+
+    pub fn store(&self, dir: &Path) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(CACHE_FILE), serde_json::to_string(self)?)
+    }
+
+    pub fn file(&self, path: &Path, key: u64) -> Option<&FileEntry> {
+        self.files.get(path).filter(|entry| entry.key == key)
+    }
+
+    /// The entry of the group if none of its files changed, a group whose file entries are missing, e.g.
+    /// in a hand-edited cache, is a miss.
+    pub fn group(&self, path: &Path, members: &[(PathBuf, u64)]) -> Option<&GroupEntry> {
+        self.groups.get(path)
+            .filter(|entry| entry.members == members)
+            .filter(|_| members.iter().all(|(file, key)| self.file(file, *key).is_some()))
+    }
+
+    pub fn insert_file(&mut self, path: PathBuf, entry: FileEntry) {
+        self.files.insert(path, entry);
+    }
+
+    pub fn insert_group(&mut self, path: PathBuf, entry: GroupEntry) {
+        self.groups.insert(path, entry);
+    }
+
+    pub fn extend(&mut self, other: Cache) {
+        self.files.extend(other.files);
+        self.groups.extend(other.groups);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = Cache::default();
+        cache.insert_file(PathBuf::from("de/common.json"), FileEntry {
+            key: 42,
+            parsed: true,
+            diagnostics: Vec::new(),
+        });
+        cache.store(dir.path()).unwrap();
+
+        let cache = Cache::load(dir.path());
+        assert!(cache.file(Path::new("de/common.json"), 42).is_some());
+        assert!(cache.file(Path::new("de/common.json"), 43).is_none());
+    }
+
+    #[test]
+    fn group_without_file_entries_is_a_miss() {
+        let mut cache = Cache::default();
+        let members = vec![(PathBuf::from("de/common.json"), 42), (PathBuf::from("en/common.json"), 7)];
+        cache.insert_group(PathBuf::from("common.json"), GroupEntry {
+            members: members.clone(),
+            diagnostics: Vec::new(),
+        });
+        cache.insert_file(PathBuf::from("de/common.json"), FileEntry {
+            key: 42,
+            parsed: true,
+            diagnostics: Vec::new(),
+        });
+        assert!(cache.group(Path::new("common.json"), &members).is_none());
+
+        cache.insert_file(PathBuf::from("en/common.json"), FileEntry {
+            key: 7,
+            parsed: true,
+            diagnostics: Vec::new(),
+        });
+        assert!(cache.group(Path::new("common.json"), &members).is_some());
+    }
+
+    #[test]
+    fn outdated_version_is_discarded() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = Cache {
+            version: "0.0.0".to_string(),
+            ..Default::default()
+        };
+        cache.insert_file(PathBuf::from("de/common.json"), FileEntry {
+            key: 42,
+            parsed: true,
+            diagnostics: Vec::new(),
+        });
+        cache.store(dir.path()).unwrap();
+
+        let cache = Cache::load(dir.path());
+        assert!(cache.file(Path::new("de/common.json"), 42).is_none());
+    }
+}
@@ -1,41 +1,74 @@
-use std::ops::Deref;
-use colored::Colorize;
+use std::collections::BTreeMap;
+use crate::checks::suppress::{Suppressions, DIRECTIVE};
+use crate::diagnostic::{Diagnostic, Location};
 use crate::io::LoadedFile;
 use crate::parser::model::{JsonObject, JsonString, JsonType};
-use crate::util::print_lines;
 
-fn join<'a, 'b: 'a>(
-    warnings: &mut Vec<(JsonString<'a>, JsonType<'a>, JsonType<'b>)>,
+/// A key with different value types in two files, the path is the full key path followed by the index of
+/// the file of the left value.
+pub type Mismatch<'a, 'b> = (Vec<JsonString<'a>>, usize, JsonType<'a>, JsonType<'b>);
+
+/// The index of the file every entry added by [`join`] stems from by its key path, all other entries stem
+/// from the first file.
+pub type Sources<'a> = BTreeMap<Vec<&'a str>, usize>;
+
+/// Adds the entries of `right`, the file with the index, missing in `left` to `left` and records their file in
+/// `sources`, entries with different value types are collected in `warnings`.
+pub fn join<'a, 'b: 'a>(
+    warnings: &mut Vec<Mismatch<'a, 'b>>,
     left: &mut JsonObject<'a>,
-    right: &JsonObject<'b>
+    sources: &mut Sources<'a>,
+    right: &JsonObject<'b>,
+    index: usize,
 ) {
-    for (r_key, r_value) in &right.values {
+    join_at(warnings, left, sources, right, index, &[]);
+}
+
+fn join_at<'a, 'b: 'a>(
+    warnings: &mut Vec<Mismatch<'a, 'b>>,
+    left: &mut JsonObject<'a>,
+    sources: &mut Sources<'a>,
+    right: &JsonObject<'b>,
+    index: usize,
+    path: &[JsonString<'a>],
+) {
+    for (r_key, r_value) in right.values.iter().filter(|(key, _)| key.value != DIRECTIVE) {
+        let mut path = path.to_vec();
+        path.push(*r_key);
+        let keys = path.iter().map(|key| key.value).collect::<Vec<_>>();
         if let Some((_, l_value)) = left.values.iter_mut().find(
             |(l_key, _)| {
                 r_key.value == l_key.value
         }) {
             match (l_value, r_value) {
                 (JsonType::Object(l_object), JsonType::Object(r_object)) => {
-                    join(warnings, l_object, r_object);
+                    join_at(warnings, l_object, sources, r_object, index, &path);
                 }
                 (JsonType::String(_), JsonType::String(_)) => {}
                 (l, r) => {
-                    warnings.push((r_key.clone(), l.clone(), r.clone()));
+                    // The entry or one of its parents was added by the file, the longest recorded path
+                    let source = (0..=keys.len()).rev()
+                        .find_map(|length| sources.get(&keys[..length]))
+                        .copied()
+                        .unwrap_or(0);
+                    warnings.push((path, source, l.clone(), r.clone()));
                 }
             }
         } else {
+            sources.insert(keys, index);
             left.values.push((*r_key, r_value.clone()))
         }
     }
 }
 
+#[allow(clippy::ptr_arg, clippy::single_match, clippy::clone_on_copy)]
 fn compare<'a, 'b>(
     warnings: &mut Vec<Vec<JsonString<'a>>>,
     acc: &JsonObject<'a>,
     object: &JsonObject<'b>,
     path: &Vec<JsonString<'a>>,
 ) {
-    for (acc_key, acc_value) in &acc.values {
+    for (acc_key, acc_value) in acc.values.iter().filter(|(key, _)| key.value != DIRECTIVE) {
         if let Some((_, value)) = object.values.iter().find(|(key, _)| acc_key.value == key.value) {
             match (acc_value, value) {
                 (JsonType::Object(acc_obj), JsonType::Object(obj)) => {
@@ -53,77 +86,90 @@ fn compare<'a, 'b>(
     }
 }
 
+/// Reports the keys missing in some of the files and the keys which are a string in one file and an object
+/// in another. Keys disabled for `missing-key` or `type-mismatch` by `suppressions` or by a directive of any file are skipped.
 pub fn entry_parity<'a>(
-    files: &Vec<(&'a LoadedFile, JsonObject<'a>)>,
-    errors: &mut Vec<String>,
+    files: &[(&'a LoadedFile, JsonObject<'a>)],
+    suppressions: &Suppressions,
+    errors: &mut Vec<Diagnostic>,
 ) {
+    let mut suppressions = suppressions.clone();
+    for (_, object) in files {
+        suppressions.collect(object);
+    }
     if let Some(((_, l_object), right)) = files.split_first() {
         let mut acc = l_object.clone();
-        for (r_file, r_object) in right {
+        let mut sources = Sources::new();
+        for (index, (r_file, r_object)) in right.iter().enumerate() {
             let mut join_warnings = Vec::new();
-            join(&mut join_warnings, &mut acc, r_object);
-
-            for (key, l_type, r_type) in join_warnings {
-                match (l_type, r_type) {
-                    (JsonType::Object(l_object), JsonType::String(r_string)) => {
-                        let (l_file, _) = files.iter()
-                            .find(|(_, object)| *object.start.deref() == *l_object.start.deref())
-                            .unwrap();
-                        let l_path = l_file.path();
-                        let l_content = l_file.content();
-                        let r_path = r_file.path();
-                        let r_content = r_file.content();
-
-                        errors.push(format!(
-                            "[{}] Found different value types for key \"{}\"\n{}\n{}\n{}\n{}",
-                            "PAIRITY".yellow(),
-                            key.value,
-                            l_path.to_str().unwrap().green(),
-                            print_lines(l_object.start.location_line()..l_object.end.location_line(), l_content),
-                            r_path.to_str().unwrap().green(),
-                            print_lines(r_string.start.location_line()..r_string.end.location_line(), r_content),
-                        ));
-                    }
-                    (JsonType::String(l_string), JsonType::Object(r_object)) => {
-                        let (l_file, _) = files.iter()
-                            .find(|(_, object)|
-                                *object.start.deref() == *l_object.start.deref()
-                            )
-                            .unwrap();
-                        let l_path = l_file.path();
-                        let l_content = l_file.content();
-                        let r_path = r_file.path();
-                        let r_content = r_file.content();
+            join(&mut join_warnings, &mut acc, &mut sources, r_object, index + 1);
 
-                        errors.push(format!(
-                            "[{}] Found different value types for key \"{}\"\n{}\n{}\n{}\n{}",
-                            "PAIRITY".yellow(),
-                            key.value,
-                            l_path.to_str().unwrap().green(),
-                            print_lines(l_string.start.location_line()..l_string.end.location_line(), l_content),
-                            r_path.to_str().unwrap().green(),
-                            print_lines(r_object.start.location_line()..r_object.end.location_line(), r_content),
-                        ));
-                    }
-                    _ => panic!(),
+            for (path, source, l_type, r_type) in join_warnings {
+                let key = path.iter().map(|key| key.value.to_string()).collect::<Vec<_>>();
+                if suppressions.is_suppressed(&key, "type-mismatch") {
+                    continue;
                 }
+                let (l_start, l_end) = l_type.span();
+                let (r_start, r_end) = r_type.span();
+
+                errors.push(Diagnostic::TypeMismatch {
+                    key,
+                    left: Location::span(files[source].0.path(), &l_start, &l_end),
+                    right: Location::span(r_file.path(), &r_start, &r_end),
+                });
             }
         }
 
         for (file, object) in files {
             let mut compare_warnings = Vec::new();
             compare(&mut compare_warnings, &acc, object, &Vec::new());
+            compare_warnings.sort_by(|a, b| {
+                a.iter().map(|key| key.value).cmp(b.iter().map(|key| key.value))
+            });
             for compare_warning in compare_warnings {
-                errors.push(format!(
-                    "[{}] Can not find key `{}` in file {}",
-                    "PAIRITY".yellow(),
-                    compare_warning.iter()
-                        .map(|p| format!("\"{}\"", p.value))
-                        .collect::<Vec<_>>()
-                        .join("->").bold(),
-                    file.path().to_str().unwrap().green(),
-                ));
+                let key = compare_warning.iter()
+                    .map(|p| p.value.to_string())
+                    .collect::<Vec<_>>();
+                if suppressions.is_suppressed(&key, "missing-key") {
+                    continue;
+                }
+                errors.push(Diagnostic::MissingKey {
+                    file: file.path().clone(),
+                    key,
+                });
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::fixture;
+    use super::*;
+
+    #[test]
+    fn mismatches_name_both_files_and_the_full_path() {
+        let files = fixture::files(&[
+            ("en.json", "{\"a\": \"A\", \"x\": {\"y\": \"Y\"}}"),
+            ("de.json", "{\"a\": \"A\", \"b\": {\"c\": \"C\"}, \"n\": {\"m\": \"M\"}, \"x\": {\"y\": \"Y\"}}"),
+            ("fr.json", "{\"a\": \"A\", \"b\": \"B\", \"n\": {\"m\": {}}, \"x\": {\"y\": {\"z\": \"Z\"}}}"),
+        ]);
+        let files = fixture::parsed(&files);
+
+        let mut errors = Vec::new();
+        entry_parity(&files, &Suppressions::default(), &mut errors);
+        let mismatches = errors.iter()
+            .filter_map(|error| match error {
+                Diagnostic::TypeMismatch { key, left, right } => Some((key.join("."), left.file.clone(), right.file.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(mismatches, vec![
+            ("b".to_string(), PathBuf::from("de.json"), PathBuf::from("fr.json")),
+            ("n.m".to_string(), PathBuf::from("de.json"), PathBuf::from("fr.json")),
+            ("x.y".to_string(), PathBuf::from("en.json"), PathBuf::from("fr.json")),
+        ]);
+    }
+}
@@ -1,12 +1,15 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
-use colored::Colorize;
+use crate::diagnostic::Diagnostic;
 use crate::io::File;
 
+/// Groups the files of every folder by their path relative to the folder.
+/// The groups are ordered by that relative path and the files of a group follow the order of `files`.
+#[allow(clippy::extra_unused_lifetimes, clippy::needless_borrows_for_generic_args, clippy::unwrap_or_default)]
 pub fn file_parity<'a>(
     files: Vec<(PathBuf, Vec<File>)>,
-    errors: &mut Vec<String>,
-) -> Vec<Vec<File>> {
+    errors: &mut Vec<Diagnostic>,
+) -> Vec<(PathBuf, Vec<File>)> {
     let mut reference_content: BTreeSet<PathBuf> = BTreeSet::new();
     for (folder, files) in &files {
         for file in files {
@@ -16,7 +19,7 @@ pub fn file_parity<'a>(
         }
     }
 
-    let mut files_of_type: HashMap<PathBuf, Vec<File>> = HashMap::new();
+    let mut files_of_type: BTreeMap<PathBuf, Vec<File>> = BTreeMap::new();
     for (folder, files) in files {
         let mut expected = reference_content.clone();
         for file in files {
@@ -25,14 +28,43 @@ pub fn file_parity<'a>(
             expected.remove(&file_name);
         }
         for not_found in expected {
-            errors.push(format!(
-                "[{}] File \"{}\" not found in folder \"{}\"",
-                "NOT FOUND".yellow(),
-                not_found.to_str().unwrap().green(),
-                folder.to_str().unwrap().green(),
-            ));
+            errors.push(Diagnostic::NotFound {
+                file: not_found,
+                folder: folder.clone(),
+            });
         }
     }
 
-    files_of_type.into_iter().map(|(_, files)| files).collect()
+    files_of_type.into_iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::io::read_folders;
+
+    #[test]
+    fn groups_are_ordered() {
+        let folders = vec![
+            PathBuf::from("testdata/i18n/fr"),
+            PathBuf::from("testdata/i18n/de"),
+            PathBuf::from("testdata/i18n/en"),
+        ];
+        let mut errors = Vec::new();
+        let groups = file_parity(read_folders(&folders), &mut errors);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            groups.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>(),
+            vec![PathBuf::from("common.json"), PathBuf::from("main/editor.json")],
+        );
+        assert_eq!(
+            groups[0].1.iter().map(|file| file.path().clone()).collect::<Vec<_>>(),
+            vec![
+                PathBuf::from("testdata/i18n/fr/common.json"),
+                PathBuf::from("testdata/i18n/de/common.json"),
+                PathBuf::from("testdata/i18n/en/common.json"),
+            ],
+        );
+    }
 }
@@ -1,36 +1,43 @@
-use colored::Colorize;
+use crate::checks::suppress::{check_directives, path_at, Suppressions};
+use crate::diagnostic::{Diagnostic, Location};
 use crate::io::LoadedFile;
-use crate::parser::model::{JsonObject, JsonStyle};
+use crate::parser::model::JsonObject;
 use crate::parser::parser::Parser;
-use crate::util::print_lines;
 
-pub fn file_style<'a, 'b, 'c>(
-    style: &'b JsonStyle,
+/// Parses the file with `parser` and collects its style violations and invalid directives, a file which can not be
+/// parsed yields `None`. Violations disabled by `suppressions` or by the directives of the file are skipped.
+pub fn file_style<'a>(
+    parser: &Parser,
     file: &'a LoadedFile,
-    errors: &mut Vec<String>,
-) -> Result<JsonObject<'a>, ()> {
-    match Parser::new(*style).parse(file.content()) {
+    suppressions: &Suppressions,
+    errors: &mut Vec<Diagnostic>,
+) -> Option<JsonObject<'a>> {
+    match parser.parse(file.content()) {
         Ok((json, style_errors)) => {
-            for (span, error_text) in style_errors {
-                let line = span.location_line();
-                errors.push(format!(
-                    "[{}] {}\n {}\n{}",
-                    format!("STYLE").yellow(),
-                    error_text,
-                    format!("{}", file.path().to_str().unwrap()).green(),
-                    print_lines(line..line, file.content()),
-                ));
+            let mut suppressions = suppressions.clone();
+            suppressions.collect(&json);
+            check_directives(file, &json, errors);
+            for style_error in style_errors.iter().filter(|error| !suppressions.suppresses_style(&json, error)) {
+                let line = style_error.span.location_line();
+                errors.push(Diagnostic::Style {
+                    kind: style_error.kind,
+                    key: path_at(&json, style_error.span.location_offset()),
+                    location: Location {
+                        file: file.path().clone(),
+                        start_line: line,
+                        end_line: line,
+                        column: style_error.span.get_column(),
+                    },
+                });
             }
-            Ok(json)
+            Some(json)
         },
         Err(e) => {
-            errors.push(format!(
-                "[{}] {}\n{}",
-                format!("ERROR").red(),
-                "Can not parse json",
-                e
-            ));
-            Err(())
+            errors.push(Diagnostic::Parse {
+                file: file.path().clone(),
+                message: format!("{}", e),
+            });
+            None
         }
     }
 }
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use glob::Pattern;
+use unicode_segmentation::UnicodeSegmentation;
+use crate::diagnostic::{Diagnostic, Location};
+use crate::io::LoadedFile;
+use crate::parser::document::unescape;
+use crate::parser::model::{JsonObject, JsonString, JsonType};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LengthLimit {
+    /// The value may have at most this many characters
+    Max(usize),
+    /// The value may be at most this many times as long as the value of the first file
+    Ratio(f64),
+}
+
+/// A limit of the length of the values whose dotted key path matches the glob.
+#[derive(Debug, Clone)]
+pub struct LengthRule {
+    pub keys: Pattern,
+    pub limit: LengthLimit,
+}
+
+impl LengthRule {
+    /// Parses a rule written as `<glob>=<limit>`, e.g. `shared.*=20`.
+    pub fn parse(rule: &str, limit: impl Fn(&str) -> Result<LengthLimit, String>) -> Result<LengthRule, String> {
+        let (keys, value) = rule.rsplit_once('=')
+            .ok_or_else(|| format!("expected <glob>=<limit> but found \"{}\"", rule))?;
+
+        Ok(LengthRule {
+            keys: Pattern::new(keys).map_err(|e| e.to_string())?,
+            limit: limit(value)?,
+        })
+    }
+}
+
+impl LengthLimit {
+    pub fn max(value: &str) -> Result<LengthLimit, String> {
+        usize::from_str(value).map(LengthLimit::Max).map_err(|e| e.to_string())
+    }
+
+    /// Parses a ratio, which has to be a finite number greater than zero.
+    pub fn ratio(value: &str) -> Result<LengthLimit, String> {
+        match f64::from_str(value).map_err(|e| e.to_string())? {
+            ratio if ratio.is_finite() && ratio > 0.0 => Ok(LengthLimit::Ratio(ratio)),
+            _ => Err(format!("expected a ratio greater than zero but found \"{}\"", value)),
+        }
+    }
+}
+
+/// The number of grapheme clusters, the characters as perceived by a reader.
+fn graphemes(value: &str) -> usize {
+    unescape(value).graphemes(true).count()
+}
+
+fn leaves<'a>(object: &JsonObject<'a>, path: &[String], found: &mut BTreeMap<Vec<String>, JsonString<'a>>) {
+    for (key, value) in &object.values {
+        let mut path = path.to_vec();
+        path.push(key.value.to_string());
+        match value {
+            JsonType::Object(child) => leaves(child, &path, found),
+            JsonType::String(string) => {
+                found.insert(path, *string);
+            }
+        }
+    }
+}
+
+/// Reports the string values exceeding the length limits of the `rules` matching their key. Ratios
+/// compare against the value of the same key in the first file, the reference.
+pub fn length<'a>(
+    files: &[(&'a LoadedFile, JsonObject<'a>)],
+    rules: &[LengthRule],
+    errors: &mut Vec<Diagnostic>,
+) {
+    if rules.is_empty() {
+        return;
+    }
+    let files = files.iter()
+        .map(|(file, object)| {
+            let mut found = BTreeMap::new();
+            leaves(object, &[], &mut found);
+            (file, found)
+        })
+        .collect::<Vec<_>>();
+    let Some((reference_file, reference)) = files.first() else {
+        return;
+    };
+
+    for (index, (file, values)) in files.iter().enumerate() {
+        for (key, string) in values {
+            let dotted = key.join(".");
+            let length = graphemes(string.value);
+            for rule in rules.iter().filter(|rule| rule.keys.matches(&dotted)) {
+                let (max, reference) = match rule.limit {
+                    LengthLimit::Max(max) => (max, None),
+                    LengthLimit::Ratio(ratio) => {
+                        let Some(reference) = reference.get(key).filter(|_| index > 0) else {
+                            continue;
+                        };
+                        let reference_length = graphemes(reference.value);
+                        if reference_length == 0 {
+                            continue;
+                        }
+
+                        (
+                            (reference_length as f64 * ratio).floor() as usize,
+                            Some(Location::span(reference_file.path(), &reference.start, &reference.end)),
+                        )
+                    }
+                };
+                if length > max {
+                    errors.push(Diagnostic::Length {
+                        key: key.clone(),
+                        location: Location::span(file.path(), &string.start, &string.end),
+                        length,
+                        max,
+                        reference,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::fixture;
+    use super::*;
+
+    #[test]
+    fn lengths_in_graphemes() {
+        let files = fixture::files(&[
+            ("en.json", "{\"a\": {\"b\": \"Save\"}, \"c\": \"Editor\"}"),
+            ("de.json", "{\"a\": {\"b\": \"Speichern\"}, \"c\": \"Bearbeiter\"}"),
+            ("ja.json", "{\"a\": {\"b\": \"保存する\"}, \"c\": \"e\u{301}diteur\"}"),
+        ]);
+        let files = fixture::parsed(&files);
+        let rules = [
+            LengthRule::parse("a.*=2", LengthLimit::ratio).unwrap(),
+            LengthRule::parse("c=7", LengthLimit::max).unwrap(),
+        ];
+
+        let mut errors = Vec::new();
+        length(&files, &rules, &mut errors);
+        let found = errors.iter()
+            .map(|error| match error {
+                Diagnostic::Length { key, location, length, max, reference } => {
+                    (key.join("."), location.file.clone(), *length, *max, reference.is_some())
+                }
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec![
+            ("a.b".to_string(), PathBuf::from("de.json"), 9, 8, true),
+            ("c".to_string(), PathBuf::from("de.json"), 10, 7, false),
+        ]);
+    }
+
+    #[test]
+    fn parse_rules() {
+        assert!(LengthRule::parse("a", LengthLimit::max).is_err());
+        assert!(LengthRule::parse("a=x", LengthLimit::max).is_err());
+        assert_eq!(LengthRule::parse("a=b=1.5", LengthLimit::ratio).unwrap().limit, LengthLimit::Ratio(1.5));
+        for ratio in ["NaN", "inf", "-1", "0"] {
+            assert!(LengthLimit::ratio(ratio).is_err());
+        }
+    }
+}
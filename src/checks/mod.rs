@@ -1,3 +1,9 @@
 pub mod file_parity;
 pub mod file_style;
 pub mod entry_parity;
+pub mod unused;
+pub mod undefined;
+pub mod untranslated;
+pub mod values;
+pub mod length;
+pub mod suppress;
@@ -0,0 +1,270 @@
+use glob::Pattern;
+use crate::diagnostic::{self, Diagnostic, Location};
+use crate::io::LoadedFile;
+use crate::parser::error::StyleError;
+use crate::parser::model::{JsonObject, JsonType};
+
+/// The key of the directive disabling rules for the object containing it and all of its children,
+/// e.g. `"//jsoncpl-disable": "missing-key, sorting"`.
+pub const DIRECTIVE: &str = "//jsoncpl-disable";
+
+/// Disables the rule with the ID for the keys whose dotted key path matches the glob and their children.
+#[derive(Debug, Clone)]
+pub struct IgnoreRule {
+    pub keys: Pattern,
+    pub rule: &'static str,
+}
+
+/// The rules disabled by the configuration and by the directives found in the files.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRule {
+    /// Parses a rule written as `<glob>=<rule>`, e.g. `legacy.*=missing-key`.
+    pub fn parse(rule: &str) -> Result<IgnoreRule, String> {
+        let (keys, value) = rule.rsplit_once('=')
+            .ok_or_else(|| format!("expected <glob>=<rule> but found \"{}\"", rule))?;
+
+        Ok(IgnoreRule {
+            keys: Pattern::new(keys).map_err(|e| e.to_string())?,
+            rule: diagnostic::rule(value)?,
+        })
+    }
+}
+
+/// The path of the innermost entry of `object` containing `offset`.
+pub(crate) fn path_at(object: &JsonObject, offset: usize) -> Vec<String> {
+    for (key, value) in &object.values {
+        let end = match value {
+            JsonType::Object(child) => child.end.location_offset(),
+            JsonType::String(string) => string.end.location_offset(),
+        };
+        if (key.start.location_offset()..=end).contains(&offset) {
+            let mut path = vec![key.value.to_string()];
+            if let JsonType::Object(child) = value {
+                path.extend(path_at(child, offset));
+            }
+            return path;
+        }
+    }
+
+    Vec::new()
+}
+
+/// Reports the directives of `object` and of its children which list an unknown rule or are no string, the
+/// known rules of a directive are disabled nevertheless.
+pub fn check_directives(file: &LoadedFile, object: &JsonObject, errors: &mut Vec<Diagnostic>) {
+    check_directives_at(file, object, &[], errors);
+}
+
+fn check_directives_at(file: &LoadedFile, object: &JsonObject, path: &[String], errors: &mut Vec<Diagnostic>) {
+    for (key, value) in &object.values {
+        match value {
+            JsonType::String(string) if key.value == DIRECTIVE => {
+                for rule in string.value.split(',') {
+                    if let Err(message) = diagnostic::rule(rule.trim()) {
+                        errors.push(Diagnostic::InvalidDirective {
+                            key: path.to_vec(),
+                            location: Location::span(file.path(), &string.start, &string.end),
+                            message,
+                        });
+                    }
+                }
+            }
+            JsonType::Object(child) if key.value == DIRECTIVE => {
+                errors.push(Diagnostic::InvalidDirective {
+                    key: path.to_vec(),
+                    location: Location::span(file.path(), &child.start, &child.end),
+                    message: "expected a string listing the IDs of rules".to_string(),
+                });
+            }
+            JsonType::Object(child) => {
+                let mut path = path.to_vec();
+                path.push(key.value.to_string());
+                check_directives_at(file, child, &path, errors);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Suppressions {
+    pub fn new(rules: Vec<IgnoreRule>) -> Suppressions {
+        Suppressions { rules }
+    }
+
+    /// Adds the rules of the directives of `object` and of its children.
+    pub fn collect(&mut self, object: &JsonObject) {
+        self.collect_at(object, &[]);
+    }
+
+    fn collect_at(&mut self, object: &JsonObject, path: &[String]) {
+        for (key, value) in &object.values {
+            match value {
+                JsonType::String(string) if key.value == DIRECTIVE => {
+                    let keys = Pattern::new(&Pattern::escape(&path.join("."))).unwrap();
+                    for rule in string.value.split(',').filter_map(|rule| diagnostic::rule(rule.trim()).ok()) {
+                        self.rules.push(IgnoreRule {
+                            keys: keys.clone(),
+                            rule,
+                        });
+                    }
+                }
+                JsonType::Object(child) => {
+                    let mut path = path.to_vec();
+                    path.push(key.value.to_string());
+                    self.collect_at(child, &path);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds the rules of the directives of `object` and removes the directives, so later checks do not
+    /// treat them as translations.
+    pub fn extract(&mut self, object: &mut JsonObject) {
+        self.collect(object);
+        Suppressions::strip(object);
+    }
+
+    /// Removes the directives from `object` and its children.
+    pub fn strip(object: &mut JsonObject) {
+        object.values.retain(|(key, _)| key.value != DIRECTIVE);
+        for (_, value) in &mut object.values {
+            if let JsonType::Object(child) = value {
+                Suppressions::strip(child);
+            }
+        }
+    }
+
+    /// Whether the rule with the ID is disabled for the key or one of its parents.
+    pub fn is_suppressed(&self, key: &[String], rule: &str) -> bool {
+        (0..=key.len()).any(|length| {
+            let dotted = key[..length].join(".");
+            self.rules.iter().any(|ignore| ignore.rule == rule && ignore.keys.matches(&dotted))
+        })
+    }
+
+    /// Whether the style violation found while parsing `object` is disabled for the entry it occurred in.
+    pub fn suppresses_style(&self, object: &JsonObject, error: &StyleError) -> bool {
+        let key = path_at(object, error.span.location_offset());
+
+        self.is_suppressed(&key, error.kind.rule())
+    }
+
+    /// Whether the rule of the diagnostic is disabled for the key it concerns.
+    pub fn suppresses(&self, diagnostic: &Diagnostic) -> bool {
+        diagnostic.key().is_some_and(|key| self.is_suppressed(key, diagnostic.rule()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::checks::entry_parity::entry_parity;
+    use crate::checks::file_style::file_style;
+    use crate::diagnostic::Diagnostic;
+    use crate::io::LoadedFile;
+    use crate::parser::model::{JsonStyle, LineEnding, SortAlgorithm, SortOrder};
+    use crate::parser::parser::Parser;
+    use super::*;
+
+    fn key(dotted: &str) -> Vec<String> {
+        dotted.split('.').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn directives_disable_subtrees() {
+        let content = "{\"a\": {\"//jsoncpl-disable\": \"missing-key, sorting, unknown\", \"b\": \"B\"}, \"c\": \"C\"}";
+        let (mut json, _) = Parser::new(JsonStyle::IGNORE).parse(content).unwrap();
+        let mut suppressions = Suppressions::new(vec![IgnoreRule::parse("c=indentation").unwrap()]);
+        suppressions.extract(&mut json);
+
+        assert!(suppressions.is_suppressed(&key("a"), "missing-key"));
+        assert!(suppressions.is_suppressed(&key("a.b.d"), "sorting"));
+        assert!(!suppressions.is_suppressed(&key("a.b"), "indentation"));
+        assert!(!suppressions.is_suppressed(&key("c"), "missing-key"));
+        assert!(suppressions.is_suppressed(&key("c"), "indentation"));
+        let JsonType::Object(a) = &json.values[0].1 else { unreachable!() };
+        assert_eq!(a.values.len(), 1);
+    }
+
+    #[test]
+    fn report_invalid_directives() {
+        let file = LoadedFile::new(
+            PathBuf::from("en.json"),
+            "{\"a\": {\"//jsoncpl-disable\": \"missing-key, missing-keys\"}, \"b\": {\"//jsoncpl-disable\": {}}}".to_string(),
+        );
+        let (json, _) = Parser::new(JsonStyle::IGNORE).parse(file.content()).unwrap();
+        let mut errors = Vec::new();
+        check_directives(&file, &json, &mut errors);
+
+        let invalid = errors.iter()
+            .filter_map(|error| match error {
+                Diagnostic::InvalidDirective { key, message, .. } => Some((key.join("."), message.split(',').next().unwrap())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(invalid, vec![
+            ("a".to_string(), "unknown rule \"missing-keys\""),
+            ("b".to_string(), "expected a string listing the IDs of rules"),
+        ]);
+    }
+
+    #[test]
+    fn checks_honour_suppressions() {
+        let style = JsonStyle::STYLED {
+            line_endings: LineEnding::LF,
+            indentation: Some("    "),
+            post_colon: Some(" "),
+            sort_algorithm: SortAlgorithm::NORMAL,
+            key_order: None,
+            order: SortOrder::ASC,
+        };
+        let en = LoadedFile::new(
+            PathBuf::from("en.json"),
+            "{\n    \"a\": {\n        \"//jsoncpl-disable\": \"missing-key, sorting\",\n        \"z\": \"Z\",\n        \"b\": \"B\"\n    },\n    \"c\": \"C\"\n}".to_string(),
+        );
+        let de = LoadedFile::new(PathBuf::from("de.json"), "{\"a\": {\"b\": \"B\"}, \"d\": \"D\"}".to_string());
+        let suppressions = Suppressions::new(vec![IgnoreRule::parse("d=missing-key").unwrap()]);
+
+        let mut errors = Vec::new();
+        let json = file_style(&Parser::new(style), &en, &suppressions, &mut errors).unwrap();
+        assert!(errors.is_empty());
+
+        let files = [(&en, json), (&de, Parser::new(JsonStyle::IGNORE).parse(de.content()).unwrap().0)];
+        entry_parity(&files, &suppressions, &mut errors);
+        assert_eq!(errors, vec![Diagnostic::MissingKey {
+            file: PathBuf::from("de.json"),
+            key: vec!["c".to_string()],
+        }]);
+    }
+
+    #[test]
+    fn suppress_diagnostics_by_rule() {
+        let suppressions = Suppressions::new(vec![IgnoreRule::parse("legacy=untranslated").unwrap()]);
+        let untranslated = |key: &str| Diagnostic::Untranslated {
+            key: self::key(key),
+            location: crate::diagnostic::Location {
+                file: PathBuf::from("de.json"),
+                start_line: 1,
+                end_line: 1,
+                column: 1,
+            },
+            reference: PathBuf::from("en.json"),
+        };
+
+        assert!(suppressions.suppresses(&untranslated("legacy.title")));
+        assert!(!suppressions.suppresses(&untranslated("title")));
+        assert!(!suppressions.suppresses(&Diagnostic::Parse { file: PathBuf::from("de.json"), message: String::new() }));
+    }
+
+    #[test]
+    fn parse_rules() {
+        assert!(IgnoreRule::parse("a.*").is_err());
+        assert!(IgnoreRule::parse("a.*=PARITY").is_err());
+        assert_eq!(IgnoreRule::parse("a.*=missing-key").unwrap().rule, "missing-key");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::diagnostic::{Diagnostic, Location};
+use crate::io::LoadedFile;
+use crate::parser::model::JsonObject;
+use crate::parser::path::KeyPath;
+use crate::usage::Usages;
+
+/// Reports the keys used in the source code which are missing in `file` and in the other files of its folder,
+/// `siblings`. Keys built at runtime are not checked.
+pub fn undefined<'a>(
+    file: &'a LoadedFile,
+    object: &JsonObject<'a>,
+    siblings: &[JsonObject],
+    usages: &Usages,
+    errors: &mut Vec<Diagnostic>,
+) {
+    for (key, used) in &usages.keys {
+        let Ok(path) = KeyPath::parse(key) else {
+            continue;
+        };
+        if object.get(&path).is_none() && siblings.iter().all(|sibling| sibling.get(&path).is_none()) {
+            errors.push(Diagnostic::UndefinedKey {
+                file: file.path().clone(),
+                key: path.segments().to_vec(),
+                usages: used.iter()
+                    .map(|usage| Location {
+                        file: usage.file.clone(),
+                        start_line: usage.line,
+                        end_line: usage.line,
+                        column: usage.column,
+                    })
+                    .collect(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::parser::model::JsonStyle;
+    use crate::parser::parser::Parser;
+    use crate::usage::{patterns, scan};
+    use super::*;
+
+    #[test]
+    fn undefined_keys() {
+        let en = LoadedFile::new(PathBuf::from("en.json"), "{\"a\": {\"b\": \"1\"}}".to_string());
+        let (json, _) = Parser::new(JsonStyle::IGNORE).parse(en.content()).unwrap();
+        let mut usages = Usages::default();
+        scan(PathBuf::from("app.ts"), "t('a'); t('a.b');\nt('a.c'); t('d.' + x)", &patterns(&[]).unwrap(), &mut usages);
+
+        let mut errors = Vec::new();
+        undefined(&en, &json, &[], &usages, &mut errors);
+        assert_eq!(errors, vec![Diagnostic::UndefinedKey {
+            file: PathBuf::from("en.json"),
+            key: vec!["a".to_string(), "c".to_string()],
+            usages: vec![Location {
+                file: PathBuf::from("app.ts"),
+                start_line: 2,
+                end_line: 2,
+                column: 4,
+            }],
+        }]);
+    }
+
+    #[test]
+    fn keys_of_sibling_files_are_defined() {
+        let common = LoadedFile::new(PathBuf::from("en/common.json"), "{\"SAVE\": \"Save\"}".to_string());
+        let (json, _) = Parser::new(JsonStyle::IGNORE).parse(common.content()).unwrap();
+        let (main, _) = Parser::new(JsonStyle::IGNORE).parse("{\"TITLE\": \"Title\"}").unwrap();
+        let mut usages = Usages::default();
+        scan(PathBuf::from("app.ts"), "t('SAVE'); t('TITLE'); t('CANCEL')", &patterns(&[]).unwrap(), &mut usages);
+
+        let mut errors = Vec::new();
+        undefined(&common, &json, &[main], &usages, &mut errors);
+        assert_eq!(
+            errors.iter().filter_map(Diagnostic::key).collect::<Vec<_>>(),
+            vec![["CANCEL".to_string()]],
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use crate::diagnostic::{Diagnostic, Location};
+use crate::io::LoadedFile;
+use crate::parser::model::{JsonObject, JsonType};
+
+fn compare<'a>(
+    reference: &JsonObject<'a>,
+    object: &JsonObject<'a>,
+    path: &[String],
+    (file, reference_file): (&LoadedFile, &LoadedFile),
+    allowlist: &[String],
+    errors: &mut Vec<Diagnostic>,
+) {
+    for (key, value) in &object.values {
+        let Some((_, reference_value)) = reference.values.iter().find(|(r_key, _)| r_key.value == key.value) else {
+            continue;
+        };
+        let mut path = path.to_vec();
+        path.push(key.value.to_string());
+        match (reference_value, value) {
+            (JsonType::Object(reference), JsonType::Object(object)) => {
+                compare(reference, object, &path, (file, reference_file), allowlist, errors);
+            }
+            (JsonType::String(reference), JsonType::String(string))
+                if !string.value.is_empty()
+                    && string.value == reference.value
+                    && !allowlist.iter().any(|allowed| allowed == string.value) => {
+                errors.push(Diagnostic::Untranslated {
+                    key: path,
+                    location: Location::span(file.path(), &string.start, &string.end),
+                    reference: reference_file.path().clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reports the string values which are identical to the value of the same key in the first file,
+/// the reference. Empty values and values in `allowlist`, like brand names, are accepted.
+pub fn untranslated<'a>(
+    files: &[(&'a LoadedFile, JsonObject<'a>)],
+    allowlist: &[String],
+    errors: &mut Vec<Diagnostic>,
+) {
+    if let Some(((reference_file, reference), others)) = files.split_first() {
+        for (file, object) in others {
+            compare(reference, object, &[], (file, reference_file), allowlist, errors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::fixture;
+    use super::*;
+
+    #[test]
+    fn identical_values() {
+        let files = fixture::files(&[
+            ("en.json", "{\"a\": {\"b\": \"Save\"}, \"c\": \"Jsoncpl\", \"d\": \"\"}"),
+            ("de.json", "{\n\"a\": {\"b\": \"Save\"},\n\"c\": \"Jsoncpl\", \"d\": \"\"}"),
+            ("fr.json", "{\"a\": \"Save\", \"c\": \"Jsoncpl \"}"),
+        ]);
+        let files = fixture::parsed(&files);
+
+        let mut errors = Vec::new();
+        untranslated(&files, &["Jsoncpl".to_string()], &mut errors);
+        assert_eq!(errors, vec![Diagnostic::Untranslated {
+            key: vec!["a".to_string(), "b".to_string()],
+            location: Location {
+                file: PathBuf::from("de.json"),
+                start_line: 2,
+                end_line: 2,
+                column: 13,
+            },
+            reference: PathBuf::from("en.json"),
+        }]);
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use crate::diagnostic::Diagnostic;
+use crate::io::LoadedFile;
+use crate::parser::model::{JsonObject, JsonType};
+use crate::usage::Usages;
+
+fn leaves(object: &JsonObject, path: &[String], found: &mut BTreeSet<Vec<String>>) {
+    for (key, value) in &object.values {
+        let mut path = path.to_vec();
+        path.push(key.value.to_string());
+        match value {
+            JsonType::Object(child) => leaves(child, &path, found),
+            JsonType::String(_) => {
+                found.insert(path);
+            }
+        }
+    }
+}
+
+/// Reports the string keys of a group which are never used in the source code,
+/// a key is reported once even if several files of the group contain it.
+pub fn unused<'a>(
+    file: &Path,
+    files: &[(&'a LoadedFile, JsonObject<'a>)],
+    usages: &Usages,
+    dynamic: bool,
+    errors: &mut Vec<Diagnostic>,
+) {
+    let mut keys = BTreeSet::new();
+    for (_, object) in files {
+        leaves(object, &[], &mut keys);
+    }
+    for key in keys {
+        if !usages.uses(&key.join("."), dynamic) {
+            errors.push(Diagnostic::UnusedKey {
+                file: file.to_path_buf(),
+                key,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::fixture;
+    use crate::usage::{patterns, scan};
+    use super::*;
+
+    #[test]
+    fn unused_leaves() {
+        let files = fixture::files(&[
+            ("de.json", "{\"a\": {\"b\": \"1\", \"c\": \"2\"}, \"d\": \"3\"}"),
+            ("en.json", "{\"e\": {\"f\": \"4\"}}"),
+        ]);
+        let files = fixture::parsed(&files);
+        let mut usages = Usages::default();
+        scan(PathBuf::from("app.ts"), "t('a.b'); t('e.' + x)", &patterns(&[]).unwrap(), &mut usages);
+
+        let mut errors = Vec::new();
+        unused(Path::new("common.json"), &files, &usages, true, &mut errors);
+        assert_eq!(errors, vec![
+            Diagnostic::UnusedKey { file: PathBuf::from("common.json"), key: vec!["a".to_string(), "c".to_string()] },
+            Diagnostic::UnusedKey { file: PathBuf::from("common.json"), key: vec!["d".to_string()] },
+        ]);
+    }
+}
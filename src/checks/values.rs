@@ -0,0 +1,154 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::diagnostic::{Diagnostic, Location};
+use crate::io::LoadedFile;
+use crate::parser::document::unescape;
+use crate::parser::model::{JsonObject, JsonType};
+
+/// The checks applied to every string value, all of them are disabled by default.
+#[derive(Debug, Clone, Default)]
+pub struct ValueChecks {
+    /// Reports empty and whitespace only values
+    pub empty: bool,
+    /// Reports values starting or ending with whitespace
+    pub whitespace: bool,
+    /// Reports values containing two consecutive spaces
+    pub double_spaces: bool,
+    /// Reports values matching one of these regexes, like `TODO` or `FIXME`
+    pub markers: Vec<Regex>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValueErrorKind {
+    Empty,
+    Whitespace,
+    DoubleSpace,
+    /// The value matches the marker regex
+    Marker(String),
+}
+
+impl ValueChecks {
+    pub fn is_empty(&self) -> bool {
+        !self.empty && !self.whitespace && !self.double_spaces && self.markers.is_empty()
+    }
+
+    fn check(&self, value: &str) -> Vec<ValueErrorKind> {
+        let mut kinds = Vec::new();
+        if value.trim().is_empty() {
+            if self.empty {
+                kinds.push(ValueErrorKind::Empty);
+            }
+            return kinds;
+        }
+        if self.whitespace && value.trim() != value {
+            kinds.push(ValueErrorKind::Whitespace);
+        }
+        if self.double_spaces && value.contains("  ") {
+            kinds.push(ValueErrorKind::DoubleSpace);
+        }
+        for marker in &self.markers {
+            if marker.is_match(value) {
+                kinds.push(ValueErrorKind::Marker(marker.as_str().to_string()));
+            }
+        }
+
+        kinds
+    }
+}
+
+impl ValueErrorKind {
+    pub fn description(&self) -> String {
+        match self {
+            ValueErrorKind::Empty => "is empty".to_string(),
+            ValueErrorKind::Whitespace => "starts or ends with whitespace".to_string(),
+            ValueErrorKind::DoubleSpace => "contains two consecutive spaces".to_string(),
+            ValueErrorKind::Marker(marker) => format!("matches the marker \"{}\"", marker),
+        }
+    }
+}
+
+fn check_object(
+    file: &LoadedFile,
+    object: &JsonObject,
+    path: &[String],
+    checks: &ValueChecks,
+    errors: &mut Vec<Diagnostic>,
+) {
+    for (key, value) in &object.values {
+        let mut path = path.to_vec();
+        path.push(key.value.to_string());
+        match value {
+            JsonType::Object(child) => check_object(file, child, &path, checks, errors),
+            JsonType::String(string) => {
+                for kind in checks.check(&unescape(string.value)) {
+                    errors.push(Diagnostic::Value {
+                        kind,
+                        key: path.clone(),
+                        location: Location::span(file.path(), &string.start, &string.end),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Applies the value `checks` to every string value of the file.
+pub fn values<'a>(
+    file: &'a LoadedFile,
+    object: &JsonObject<'a>,
+    checks: &ValueChecks,
+    errors: &mut Vec<Diagnostic>,
+) {
+    if !checks.is_empty() {
+        check_object(file, object, &[], checks, errors);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use crate::parser::model::JsonStyle;
+    use crate::parser::parser::Parser;
+    use super::*;
+
+    fn check(content: &str, checks: &ValueChecks) -> Vec<(Vec<String>, ValueErrorKind, u32)> {
+        let file = LoadedFile::new(PathBuf::from("en.json"), content.to_string());
+        let (json, _) = Parser::new(JsonStyle::IGNORE).parse(file.content()).unwrap();
+        let mut errors = Vec::new();
+        values(&file, &json, checks, &mut errors);
+
+        errors.into_iter()
+            .map(|error| match error {
+                Diagnostic::Value { kind, key, location } => (key, kind, location.start_line),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_check() {
+        let checks = ValueChecks {
+            empty: true,
+            whitespace: true,
+            double_spaces: true,
+            markers: vec![Regex::new(r"\bTODO\b").unwrap()],
+        };
+        let errors = check(
+            "{\n\"a\": \"\",\n\"b\": {\"c\": \" \"},\n\"d\": \"Save\\n\",\n\"e\": \"Save  all\",\n\"f\": \"TODO: translate\",\n\"g\": \"TODOS\"\n}",
+            &checks,
+        );
+
+        assert_eq!(errors, vec![
+            (vec!["a".to_string()], ValueErrorKind::Empty, 2),
+            (vec!["b".to_string(), "c".to_string()], ValueErrorKind::Empty, 3),
+            (vec!["d".to_string()], ValueErrorKind::Whitespace, 4),
+            (vec!["e".to_string()], ValueErrorKind::DoubleSpace, 5),
+            (vec!["f".to_string()], ValueErrorKind::Marker(r"\bTODO\b".to_string()), 6),
+        ]);
+    }
+
+    #[test]
+    fn disabled_checks() {
+        assert!(check("{\"a\": \"\", \"b\": \" x  y \"}", &ValueChecks::default()).is_empty());
+    }
+}
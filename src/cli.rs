@@ -1,5 +1,11 @@
 use std::path::PathBuf;
-use clap::{ValueEnum, Parser, Subcommand};
+use clap::{Args, ValueEnum, Parser, Subcommand};
+use jsoncpl::checks::length::{LengthLimit, LengthRule};
+use jsoncpl::checks::suppress::IgnoreRule;
+use jsoncpl::parser::naming::KeyCase;
+use jsoncpl::parser::order::{Grouping, PinnedKeys};
+use jsoncpl::severity::RuleSeverity;
+use jsoncpl::KeyPath;
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = "A tool for linting and formatting json files")]
@@ -22,9 +28,20 @@ pub struct Cli {
     /// The expected indentation of the json files
     #[arg(short, long, default_value = "four")]
     pub indent: Indentation,
+
+    /// Keys which come before the sorted keys in this order, optionally only in the objects whose dotted key
+    /// matches the glob, e.g. "$schema,$id,title,type" or "properties.*=type,title"
+    #[arg(long = "pin", value_parser = PinnedKeys::parse)]
+    pub pinned: Vec<PinnedKeys>,
+
+    /// Group the keys by the type of their value: strings-first places objects after strings, objects-first
+    /// places strings after objects
+    #[arg(long)]
+    pub group: Option<Grouping>,
 }
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Subcommand)]
+#[derive(Clone, Subcommand)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Format the provided files according to the style parameters
     Format {
@@ -35,15 +52,263 @@ pub enum Commands {
     Lint {
         /// List the folders to search for files to lint and compare
         folders: Vec<PathBuf>,
+
+        /// The number of threads used to lint the file groups (0 uses one per cpu)
+        #[arg(short, long, default_value = "0")]
+        jobs: usize,
+
+        /// Cache the results in this directory and only check files which changed since the last run
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+
+        /// Only report groups of files containing a file which changed since this git revision
+        #[arg(long)]
+        changed_since: Option<String>,
+
+        /// Report values identical to the value of the same key in the first folder
+        #[arg(long)]
+        untranslated: bool,
+
+        /// A value which may be identical in every folder, like a brand name
+        #[arg(long = "allow", requires = "untranslated")]
+        allowed: Vec<String>,
+
+        /// Report empty and whitespace only values
+        #[arg(long)]
+        empty_values: bool,
+
+        /// Report values starting or ending with whitespace
+        #[arg(long)]
+        whitespace: bool,
+
+        /// Report values containing two consecutive spaces
+        #[arg(long)]
+        double_spaces: bool,
+
+        /// Report values matching this regex, e.g. "TODO|FIXME"
+        #[arg(long = "marker")]
+        markers: Vec<String>,
+
+        /// The naming convention of keys of objects: snake, screaming-snake, camel, kebab or regex:<regex>
+        #[arg(long)]
+        object_keys: Option<KeyCase>,
+
+        /// The naming convention of keys of strings: snake, screaming-snake, camel, kebab or regex:<regex>
+        #[arg(long)]
+        leaf_keys: Option<KeyCase>,
+
+        /// Format the files and rename the keys which do not follow the naming conventions before linting
+        #[arg(long)]
+        fix: bool,
+
+        /// The maximum number of characters of the values whose dotted key matches the glob, e.g. "shared.*=20"
+        #[arg(long, value_parser = |rule: &str| LengthRule::parse(rule, LengthLimit::max))]
+        max_length: Vec<LengthRule>,
+
+        /// How many times longer than in the first folder the values whose dotted key matches the glob may be,
+        /// e.g. "*=2.5"
+        #[arg(long, value_parser = |rule: &str| LengthRule::parse(rule, LengthLimit::ratio))]
+        max_ratio: Vec<LengthRule>,
+
+        #[command(flatten)]
+        rules: RuleArgs,
+
+        /// Fail if there are more warnings than this, warnings never fail the run by default
+        #[arg(long)]
+        max_warnings: Option<usize>,
+
+        /// Only fail on diagnostics which are not recorded in this baseline file
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+
+        /// Record the current diagnostics of all groups in the baseline file instead of reporting them
+        #[arg(long, requires = "baseline", conflicts_with = "changed_since")]
+        write_baseline: bool,
+    },
+    /// Start a language server over stdio providing diagnostics, code actions and formatting
+    Lsp {
+        /// List the folders containing the files to compare
+        folders: Vec<PathBuf>,
+
+        #[command(flatten)]
+        rules: RuleArgs,
+    },
+    /// Watch the provided folders and lint or format the files of a group whenever one of them changes
+    Watch {
+        /// Whether to lint or to format the changed files
+        #[arg(value_enum)]
+        mode: WatchMode,
+
+        /// List the folders to watch
+        folders: Vec<PathBuf>,
+
+        #[command(flatten)]
+        rules: RuleArgs,
+    },
+    /// Report the keys which are never used in the source code
+    Unused {
+        /// Glob of the source files to scan for key usages, e.g. "src/**/*.ts"
+        #[arg(short, long = "source", required = true)]
+        sources: Vec<String>,
+
+        /// Regex matching a key usage, its first capture group or the group named "key" is the key
+        /// [default: translate('...'), t('...') and '...' | translate]
+        #[arg(short, long = "pattern")]
+        patterns: Vec<String>,
+
+        /// Treat keys built at runtime, like t('errors.' + code), as using every key with that prefix
+        #[arg(short, long)]
+        dynamic: bool,
+
+        /// List the folders containing the keys
+        folders: Vec<PathBuf>,
+    },
+    /// Report the keys used in the source code which are missing in the file of the first folder
+    Extract {
+        /// The path of the file relative to the folders
+        file: PathBuf,
+
+        /// Glob of the source files to scan for key usages, e.g. "src/**/*.ts"
+        #[arg(short, long = "source", required = true)]
+        sources: Vec<String>,
+
+        /// Regex matching a key usage, its first capture group or the group named "key" is the key
+        /// [default: translate('...'), t('...') and '...' | translate]
+        #[arg(short, long = "pattern")]
+        patterns: Vec<String>,
+
+        /// Insert the missing keys into the file of every folder
+        #[arg(long)]
+        insert: bool,
+
+        /// The value of inserted keys
+        #[arg(long, default_value = "")]
+        placeholder: String,
+
+        /// List the folders containing the file, the first one is the reference
+        #[arg(required = true)]
+        folders: Vec<PathBuf>,
+    },
+    /// Print the value of a key in the file of every folder
+    Get {
+        /// The path of the file relative to the folders
+        file: PathBuf,
+
+        /// The key as JSON Pointer (/a/b) or dotted path (a.b)
+        path: KeyPath,
+
+        /// List the folders containing the file
+        folders: Vec<PathBuf>,
+    },
+    /// Set a key to a string in the file of every folder, creating missing objects
+    Set {
+        /// The path of the file relative to the folders
+        file: PathBuf,
+
+        /// The key as JSON Pointer (/a/b) or dotted path (a.b)
+        path: KeyPath,
+
+        /// The string to set
+        value: String,
+
+        /// List the folders containing the file
+        folders: Vec<PathBuf>,
+    },
+    /// Remove a key from the file of every folder
+    Rm {
+        /// The path of the file relative to the folders
+        file: PathBuf,
+
+        /// The key as JSON Pointer (/a/b) or dotted path (a.b)
+        path: KeyPath,
+
+        /// List the folders containing the file
+        folders: Vec<PathBuf>,
+    },
+    /// Move a key or an entire object in the file of every folder, creating missing objects
+    Mv {
+        /// The path of the file relative to the folders
+        file: PathBuf,
+
+        /// The key to move as JSON Pointer (/a/b) or dotted path (a.b)
+        from: KeyPath,
+
+        /// The new key as JSON Pointer (/a/b) or dotted path (a.b)
+        to: KeyPath,
+
+        /// Replace the values of already existing keys
+        #[arg(short, long)]
+        force: bool,
+
+        /// List the folders containing the file
+        folders: Vec<PathBuf>,
     },
+    /// Report the number of missing, untranslated, empty and obsolete keys of every folder and file
+    Stats {
+        /// The output format of the statistics
+        #[arg(short, long, value_enum, default_value = "table")]
+        format: StatsFormat,
+
+        /// Write the statistics to this file instead of printing them
+        #[arg(long)]
+        output: Option<PathBuf>,
+
+        /// List the folders to compare, the first one is the reference
+        folders: Vec<PathBuf>,
+    },
+}
+
+/// The rules of the commands reporting diagnostics.
+#[derive(Clone, Args)]
+pub struct RuleArgs {
+    /// Disable a rule for the keys whose dotted key matches the glob and their children, e.g.
+    /// "legacy.*=missing-key"
+    #[arg(long, value_parser = IgnoreRule::parse)]
+    pub ignore: Vec<IgnoreRule>,
+
+    /// Set the severity (off, warn or error) of a rule, e.g. "sorting=warn" or "untranslated=off"
+    #[arg(long = "rule", value_parser = RuleSeverity::parse)]
+    pub rules: Vec<RuleSeverity>,
+
+    /// Read the severities and the ignored keys from this file, the options of the command line take
+    /// precedence [default: jsoncpl.json if it exists]
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum StatsFormat {
+    /// An aligned table
+    Table,
+    /// A JSON array with an entry for every folder
+    Json,
+    /// Comma separated values
+    Csv,
+    /// A self-contained page with a matrix of the keys of every file against the folders
+    Html,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum WatchMode {
+    /// Check the changed files and their counterparts
+    Lint,
+    /// Format the changed files and their counterparts
+    Format,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum SortAlgorithm {
     /// Sort the keys by natural sort
     Natural,
     /// Sort the keys by classical sort
     Default,
+    /// Sort the keys ignoring their case
+    CaseInsensitive,
+    /// Sort the keys by natural sort ignoring their case
+    NaturalCaseInsensitive,
+    /// Sort the keys by Unicode collation (CLDR root order), e.g. apple, éclair, Zebra
+    Collation,
     /// Ignore sort order
     IGNORE
 }
@@ -57,6 +322,7 @@ pub enum SortOrder {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum LineEnding {
     /// Add \r\n to the end of an entry
     CRLF,
@@ -71,6 +337,7 @@ pub enum LineEnding {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Indentation {
     /// Indent with \t
     TAB,
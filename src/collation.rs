@@ -0,0 +1,43 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use feruca::Collator;
+use crate::natural_sort;
+
+thread_local! {
+    // The collator keeps buffers between comparisons
+    static COLLATOR: RefCell<Collator> = RefCell::new(Collator::default());
+}
+
+/// Compares by the Unicode Collation Algorithm with the CLDR root order, case and accents only decide
+/// between otherwise equal strings.
+pub fn collate(s1: &str, s2: &str) -> Ordering {
+    COLLATOR.with(|collator| collator.borrow_mut().collate(s1, s2))
+}
+
+/// Compares the lowercase strings, strings differing only in case are ordered by their bytes.
+pub fn case_insensitive(s1: &str, s2: &str) -> Ordering {
+    s1.to_lowercase().cmp(&s2.to_lowercase()).then_with(|| s1.cmp(s2))
+}
+
+/// Compares the lowercase strings by natural sort, strings differing only in case are ordered by natural sort.
+pub fn natural_case_insensitive(s1: &str, s2: &str) -> Ordering {
+    natural_sort::compare(&s1.to_lowercase(), &s2.to_lowercase()).then_with(|| natural_sort::compare(s1, s2))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sorted(compare: fn(&str, &str) -> Ordering) -> Vec<&'static str> {
+        let mut keys = vec!["Zebra", "item10", "apple", "Äpfel", "item2", "Item3", "eclair", "éclair"];
+        keys.sort_by(|a, b| compare(a, b));
+        keys
+    }
+
+    #[test]
+    fn compare_algorithms() {
+        assert_eq!(sorted(case_insensitive), vec!["apple", "eclair", "item10", "item2", "Item3", "Zebra", "Äpfel", "éclair"]);
+        assert_eq!(sorted(natural_case_insensitive), vec!["apple", "eclair", "item2", "Item3", "item10", "Zebra", "Äpfel", "éclair"]);
+        assert_eq!(sorted(collate), vec!["Äpfel", "apple", "eclair", "éclair", "item10", "item2", "Item3", "Zebra"]);
+    }
+}
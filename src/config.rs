@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use glob::Pattern;
+use serde::Deserialize;
+use crate::checks::suppress::IgnoreRule;
+use crate::diagnostic;
+use crate::severity::RuleSeverity;
+
+/// The configuration file read from the working directory if no other file is given.
+pub const CONFIG_FILE: &str = "jsoncpl.json";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    rules: BTreeMap<String, String>,
+    #[serde(default)]
+    ignore: BTreeMap<String, Vec<String>>,
+}
+
+/// The settings of a configuration file, e.g.
+/// `{"rules": {"sorting": "warn"}, "ignore": {"legacy.*": ["missing-key", "type-mismatch"]}}`.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// The severities of the rules, like `--rule`
+    pub severities: Vec<RuleSeverity>,
+    /// The rules disabled for the keys matching a glob, like `--ignore`
+    pub ignore: Vec<IgnoreRule>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, String> {
+        Config::parse(&fs::read_to_string(path).map_err(|e| e.to_string())?)
+    }
+
+    pub fn parse(content: &str) -> Result<Config, String> {
+        let raw = serde_json::from_str::<RawConfig>(content).map_err(|e| e.to_string())?;
+        let severities = raw.rules.iter()
+            .map(|(rule, severity)| Ok(RuleSeverity {
+                rule: diagnostic::rule(rule)?,
+                severity: severity.parse()?,
+            }))
+            .collect::<Result<_, String>>()?;
+        let mut ignore = Vec::new();
+        for (keys, rules) in &raw.ignore {
+            let keys = Pattern::new(keys).map_err(|e| e.to_string())?;
+            for rule in rules {
+                ignore.push(IgnoreRule {
+                    keys: keys.clone(),
+                    rule: diagnostic::rule(rule)?,
+                });
+            }
+        }
+
+        Ok(Config { severities, ignore })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::severity::Severity;
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config = Config::parse(
+            "{\"rules\": {\"sorting\": \"warn\"}, \"ignore\": {\"legacy.*\": [\"missing-key\", \"type-mismatch\"]}}",
+        ).unwrap();
+        assert_eq!(config.severities, vec![RuleSeverity { rule: "sorting", severity: Severity::Warn }]);
+        assert_eq!(config.ignore.iter().map(|rule| rule.rule).collect::<Vec<_>>(), vec!["missing-key", "type-mismatch"]);
+        assert!(config.ignore[0].keys.matches("legacy.title"));
+
+        assert!(Config::parse("{}").unwrap().ignore.is_empty());
+        assert!(Config::parse("{\"rules\": {\"sort\": \"warn\"}}").is_err());
+        assert!(Config::parse("{\"ignore\": {\"a\": [\"PARITY\"]}}").is_err());
+        assert!(Config::parse("{\"jobs\": 4}").is_err());
+    }
+}
@@ -0,0 +1,290 @@
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use crate::checks::values::ValueErrorKind;
+use crate::io::LoadedFile;
+use crate::parser::error::StyleErrorKind;
+use crate::parser::model::{JsonStyle, Span};
+use crate::util::print_lines;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Location {
+    pub file: PathBuf,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub column: usize,
+}
+
+impl Location {
+    /// The location of the text from `start` to `end` in `file`.
+    pub fn span(file: &Path, start: &Span, end: &Span) -> Location {
+        Location {
+            file: file.to_path_buf(),
+            start_line: start.location_line(),
+            end_line: end.location_line(),
+            column: start.get_column(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Diagnostic {
+    NotFound {
+        file: PathBuf,
+        folder: PathBuf,
+    },
+    Parse {
+        file: PathBuf,
+        message: String,
+    },
+    Style {
+        kind: StyleErrorKind,
+        /// The path of the innermost entry containing the error, empty outside of every entry
+        key: Vec<String>,
+        location: Location,
+    },
+    MissingKey {
+        file: PathBuf,
+        key: Vec<String>,
+    },
+    TypeMismatch {
+        key: Vec<String>,
+        left: Location,
+        right: Location,
+    },
+    UnusedKey {
+        /// The path of the files relative to their folder
+        file: PathBuf,
+        key: Vec<String>,
+    },
+    UndefinedKey {
+        file: PathBuf,
+        key: Vec<String>,
+        /// Where the source code uses the key
+        usages: Vec<Location>,
+    },
+    Untranslated {
+        key: Vec<String>,
+        /// The value which is identical to the reference
+        location: Location,
+        reference: PathBuf,
+    },
+    Value {
+        kind: ValueErrorKind,
+        key: Vec<String>,
+        location: Location,
+    },
+    Length {
+        key: Vec<String>,
+        location: Location,
+        /// The length in grapheme clusters
+        length: usize,
+        max: usize,
+        /// The value the maximum is relative to
+        reference: Option<Location>,
+    },
+    InvalidDirective {
+        /// The path of the object containing the directive
+        key: Vec<String>,
+        location: Location,
+        message: String,
+    },
+}
+
+/// The IDs of the rules, the kinds of diagnostics whose severity can be configured.
+pub const RULES: [&str; 20] = [
+    "missing-file",
+    "parse",
+    "space",
+    "post-colon",
+    "line-ending",
+    "indentation",
+    "sorting",
+    "object-key-naming",
+    "leaf-key-naming",
+    "missing-key",
+    "type-mismatch",
+    "unused-key",
+    "undefined-key",
+    "untranslated",
+    "empty-value",
+    "value-whitespace",
+    "double-space",
+    "marker",
+    "length",
+    "invalid-directive",
+];
+
+/// Looks up the rule with the ID, e.g. `sorting`.
+pub fn rule(id: &str) -> Result<&'static str, String> {
+    RULES.iter()
+        .find(|known| **known == id)
+        .copied()
+        .ok_or_else(|| format!("unknown rule \"{}\", expected one of {}", id, RULES.join(", ")))
+}
+
+fn format_key(key: &[String]) -> String {
+    key.iter()
+        .map(|p| format!("\"{}\"", p))
+        .collect::<Vec<_>>()
+        .join("->")
+}
+
+impl Diagnostic {
+    /// The ID of the rule which reported the diagnostic, one of `RULES`.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Diagnostic::NotFound { .. } => "missing-file",
+            Diagnostic::Parse { .. } => "parse",
+            Diagnostic::Style { kind, .. } => kind.rule(),
+            Diagnostic::MissingKey { .. } => "missing-key",
+            Diagnostic::TypeMismatch { .. } => "type-mismatch",
+            Diagnostic::UnusedKey { .. } => "unused-key",
+            Diagnostic::UndefinedKey { .. } => "undefined-key",
+            Diagnostic::Untranslated { .. } => "untranslated",
+            Diagnostic::Value { kind, .. } => match kind {
+                ValueErrorKind::Empty => "empty-value",
+                ValueErrorKind::Whitespace => "value-whitespace",
+                ValueErrorKind::DoubleSpace => "double-space",
+                ValueErrorKind::Marker(_) => "marker",
+            },
+            Diagnostic::Length { .. } => "length",
+            Diagnostic::InvalidDirective { .. } => "invalid-directive",
+        }
+    }
+
+    /// The key path the diagnostic concerns, if it concerns a single entry.
+    pub fn key(&self) -> Option<&[String]> {
+        match self {
+            Diagnostic::NotFound { .. } | Diagnostic::Parse { .. } => None,
+            Diagnostic::Style { key, .. }
+            | Diagnostic::MissingKey { key, .. }
+            | Diagnostic::TypeMismatch { key, .. }
+            | Diagnostic::UnusedKey { key, .. }
+            | Diagnostic::UndefinedKey { key, .. }
+            | Diagnostic::Untranslated { key, .. }
+            | Diagnostic::Value { key, .. }
+            | Diagnostic::Length { key, .. }
+            | Diagnostic::InvalidDirective { key, .. } => Some(key),
+        }
+    }
+
+    /// Renders the diagnostic for the terminal, `files` provides the content for the source excerpts.
+    pub fn render(&self, style: &JsonStyle, files: &[LoadedFile]) -> String {
+        let lines = |location: &Location| {
+            files.iter()
+                .find(|file| file.path() == &location.file)
+                .map(|file| print_lines(location.start_line..location.end_line, file.content()))
+                .unwrap_or_default()
+        };
+
+        match self {
+            Diagnostic::NotFound { file, folder } => format!(
+                "[{}] File \"{}\" not found in folder \"{}\"",
+                "NOT FOUND".yellow(),
+                file.to_str().unwrap().green(),
+                folder.to_str().unwrap().green(),
+            ),
+            Diagnostic::Parse { message, .. } => format!(
+                "[{}] {}\n{}",
+                "ERROR".red(),
+                "Can not parse json",
+                message,
+            ),
+            Diagnostic::Style { kind, location, .. } => format!(
+                "[{}] {}\n {}\n{}",
+                "STYLE".yellow(),
+                kind.message(style, location.start_line, location.column),
+                location.file.to_str().unwrap().green(),
+                lines(location),
+            ),
+            Diagnostic::MissingKey { file, key } => format!(
+                "[{}] Can not find key `{}` in file {}",
+                "PAIRITY".yellow(),
+                format_key(key).bold(),
+                file.to_str().unwrap().green(),
+            ),
+            Diagnostic::TypeMismatch { key, left, right } => format!(
+                "[{}] Found different value types for key `{}`\n{}\n{}\n{}\n{}",
+                "PAIRITY".yellow(),
+                format_key(key).bold(),
+                left.file.to_str().unwrap().green(),
+                lines(left),
+                right.file.to_str().unwrap().green(),
+                lines(right),
+            ),
+            Diagnostic::UnusedKey { file, key } => format!(
+                "[{}] Key `{}` of file {} is never used",
+                "UNUSED".yellow(),
+                format_key(key).bold(),
+                file.to_str().unwrap().green(),
+            ),
+            Diagnostic::UndefinedKey { file, key, usages } => format!(
+                "[{}] Can not find used key `{}` in file {}{}",
+                "UNDEFINED".yellow(),
+                format_key(key).bold(),
+                file.to_str().unwrap().green(),
+                usages.iter()
+                    .map(|usage| format!("\n used in {}:{}:{}", usage.file.to_str().unwrap(), usage.start_line, usage.column))
+                    .collect::<String>(),
+            ),
+            Diagnostic::Untranslated { key, location, reference } => format!(
+                "[{}] The value of key `{}` is identical to the one in file {}\n {}\n{}",
+                "UNTRANSLATED".yellow(),
+                format_key(key).bold(),
+                reference.to_str().unwrap().green(),
+                location.file.to_str().unwrap().green(),
+                lines(location),
+            ),
+            Diagnostic::Value { kind, key, location } => format!(
+                "[{}] The value of key `{}` {}\n {}\n{}",
+                "VALUE".yellow(),
+                format_key(key).bold(),
+                kind.description(),
+                location.file.to_str().unwrap().green(),
+                lines(location),
+            ),
+            Diagnostic::Length { key, location, length, max, reference } => format!(
+                "[{}] The value of key `{}` has {} characters, expected at most {}{}\n {}\n{}{}",
+                "LENGTH".yellow(),
+                format_key(key).bold(),
+                length,
+                max,
+                reference.as_ref()
+                    .map(|reference| format!(" relative to file {}", reference.file.to_str().unwrap().green()))
+                    .unwrap_or_default(),
+                location.file.to_str().unwrap().green(),
+                lines(location),
+                reference.as_ref()
+                    .map(|reference| format!("\n {}\n{}", reference.file.to_str().unwrap().green(), lines(reference)))
+                    .unwrap_or_default(),
+            ),
+            Diagnostic::InvalidDirective { key, location, message } => format!(
+                "[{}] Invalid directive in `{}`: {}\n {}\n{}",
+                "DIRECTIVE".yellow(),
+                format_key(key).bold(),
+                message,
+                location.file.to_str().unwrap().green(),
+                lines(location),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_the_first_line() {
+        let file = LoadedFile::new(PathBuf::from("de.json"), "{\"a\": \"Save\"}".to_string());
+        let untranslated = Diagnostic::Untranslated {
+            key: vec!["a".to_string()],
+            location: Location { file: PathBuf::from("de.json"), start_line: 1, end_line: 1, column: 8 },
+            reference: PathBuf::from("en.json"),
+        };
+
+        let rendered = untranslated.render(&JsonStyle::IGNORE, &[file]);
+        assert!(rendered.ends_with(&format!(" [{}]{}", "1".blue(), "{\"a\": \"Save\"}".red())));
+    }
+}
@@ -0,0 +1,248 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::io::LoadedFile;
+use crate::parser::document::{JsonDocument, JsonValue};
+use crate::parser::generator::Generator;
+use crate::parser::model::JsonStyle;
+use crate::parser::parser::Parser;
+use crate::parser::path::{EditError, KeyPath};
+
+pub enum Edit {
+    /// Sets the string at the path, missing parent objects are created
+    Set(KeyPath, String),
+    /// Removes the entry at the path
+    Remove(KeyPath),
+    /// Moves the entry at the first path to the second one, existing entries are only replaced if forced
+    Move {
+        from: KeyPath,
+        to: KeyPath,
+        force: bool,
+    },
+    /// Sets the string at every path which does not exist yet, missing parent objects are created
+    Insert(Vec<KeyPath>, String),
+}
+
+impl Edit {
+    /// The key which has to exist in at least one file.
+    fn path(&self) -> Option<&KeyPath> {
+        match self {
+            Edit::Remove(path) | Edit::Move { from: path, .. } => Some(path),
+            Edit::Set(..) | Edit::Insert(..) => None,
+        }
+    }
+
+    /// Applies the edit to `document` and returns whether it found the key,
+    /// a missing key is only an error for `Set` if its parent is a string.
+    pub fn apply(&self, document: &mut JsonDocument) -> Result<bool, EditError> {
+        let result = match self {
+            Edit::Set(path, value) => document.set(path, JsonValue::from(value.as_str())).map(|_| ()),
+            Edit::Remove(path) => document.remove(path).map(|_| ()),
+            Edit::Move { from, to, force } => document.move_to(from, to, *force),
+            Edit::Insert(paths, value) => paths.iter().try_for_each(|path| match document.get(path) {
+                Some(_) => Ok(()),
+                None => document.set(path, JsonValue::from(value.as_str())).map(|_| ()),
+            }),
+        };
+        match result {
+            Ok(()) => Ok(true),
+            Err(EditError::NotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn parse(file: &LoadedFile) -> Result<JsonDocument, String> {
+    Parser::new(JsonStyle::IGNORE)
+        .parse(file.content())
+        .map(|(json, _)| JsonDocument::from(json))
+        .map_err(|e| format!("Can not parse json\n{}", e))
+}
+
+/// Looks up `path` in every file, the values are rendered in `style`.
+pub fn get(style: &JsonStyle, files: &[LoadedFile], path: &KeyPath) -> Vec<(PathBuf, Result<Option<String>, String>)> {
+    files.iter()
+        .map(|file| {
+            let value = parse(file).map(|document| document.get(path).map(|value| match value {
+                JsonValue::Object(object) => Generator::new(*style).generate(object.clone()),
+                JsonValue::String(string) => format!("\"{}\"", string),
+            }));
+
+            (file.path().clone(), value)
+        })
+        .collect()
+}
+
+/// Applies `edit` to every file and writes them in `style`. Files are only written if the edit
+/// succeeds in all of them and finds the key in at least one, otherwise the errors are returned.
+pub fn edit(style: &JsonStyle, files: &[LoadedFile], edit: &Edit) -> Result<Vec<PathBuf>, Vec<(PathBuf, String)>> {
+    let mut errors = Vec::new();
+    let mut generated = Vec::new();
+    let mut found = false;
+    for file in files {
+        match parse(file).and_then(|mut document| {
+            edit.apply(&mut document).map_err(|e| e.to_string()).map(|applied| (document, applied))
+        }) {
+            Ok((document, applied)) => {
+                found |= applied;
+                generated.push((file, Generator::new(*style).generate(document)));
+            }
+            Err(e) => errors.push((file.path().clone(), e)),
+        }
+    }
+    if let (true, false, Some(path)) = (errors.is_empty(), found, edit.path()) {
+        errors.extend(files.iter().map(|file| {
+            (file.path().clone(), EditError::NotFound(path.clone()).to_string())
+        }));
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    // Every file is written to a temporary file first, so a failing write leaves all folders untouched
+    let mut staged = Vec::new();
+    for (file, content) in generated.into_iter().filter(|(file, content)| content != file.content()) {
+        let temporary = temporary_path(file.path());
+        if let Err(e) = fs::write(&temporary, content) {
+            for (_, temporary) in staged {
+                let _ = fs::remove_file(temporary);
+            }
+            return Err(vec![(file.path().clone(), e.to_string())]);
+        }
+        staged.push((file.path().clone(), temporary));
+    }
+
+    let mut written = Vec::new();
+    for (path, temporary) in staged {
+        fs::rename(&temporary, &path).map_err(|e| vec![(path.clone(), e.to_string())])?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// The path next to `path` the new content is written to before replacing the file.
+fn temporary_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.jsoncpl", name))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{LineEnding, SortAlgorithm, SortOrder};
+    use super::*;
+
+    const STYLE: JsonStyle = JsonStyle::STYLED {
+        line_endings: LineEnding::LF,
+        indentation: Some("  "),
+        post_colon: Some(" "),
+        sort_algorithm: SortAlgorithm::NORMAL,
+        key_order: None,
+        order: SortOrder::ASC,
+    };
+
+    fn files(dir: &std::path::Path, contents: &[&str]) -> Vec<LoadedFile> {
+        contents.iter().enumerate()
+            .map(|(index, content)| {
+                let path = dir.join(format!("{}.json", index));
+                fs::write(&path, content).unwrap();
+                LoadedFile::new(path, content.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn edit_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = files(dir.path(), &["{\"b\": {\"c\": \"1\"}}", "{\"b\": {}}"]);
+
+        let written = edit(&STYLE, &files, &Edit::Move {
+            from: KeyPath::parse("b.c").unwrap(),
+            to: KeyPath::parse("a.c").unwrap(),
+            force: false,
+        });
+        assert_eq!(written, Ok(vec![files[0].path().clone(), files[1].path().clone()]));
+        assert_eq!(
+            fs::read_to_string(files[0].path()).unwrap(),
+            "{\n  \"a\": {\n    \"c\": \"1\"\n  },\n  \"b\": {\n  }\n}",
+        );
+        assert_eq!(fs::read_to_string(files[1].path()).unwrap(), "{\n  \"b\": {\n  }\n}");
+    }
+
+    #[test]
+    fn edit_nothing_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = files(dir.path(), &["{\"b\": {}}", "{\"b\": \"1\"}"]);
+
+        let errors = edit(&STYLE, &files, &Edit::Set(KeyPath::parse("b.c").unwrap(), "2".to_string()))
+            .unwrap_err();
+        assert_eq!(errors, vec![(files[1].path().clone(), "The value of key `\"b\"` is not an object".to_string())]);
+        assert_eq!(fs::read_to_string(files[0].path()).unwrap(), "{\"b\": {}}");
+
+        let errors = edit(&STYLE, &files, &Edit::Remove(KeyPath::parse("x").unwrap())).unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn edit_nothing_on_write_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = files(dir.path(), &["{}", "{}"]);
+        // The temporary file of the second file can not be written
+        fs::create_dir(temporary_path(files[1].path())).unwrap();
+
+        let errors = edit(&STYLE, &files, &Edit::Set(KeyPath::parse("a").unwrap(), "1".to_string())).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, *files[1].path());
+        assert_eq!(fs::read_to_string(files[0].path()).unwrap(), "{}");
+        assert!(!temporary_path(files[0].path()).exists());
+    }
+
+    #[test]
+    fn move_refuses_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = files(dir.path(), &["{\"a\": \"1\", \"b\": \"2\"}", "{\"b\": \"2\"}"]);
+        let errors = edit(&STYLE, &files, &Edit::Move {
+            from: KeyPath::parse("a").unwrap(),
+            to: KeyPath::parse("b").unwrap(),
+            force: false,
+        }).unwrap_err();
+        assert_eq!(errors, vec![(files[0].path().clone(), "The key `\"b\"` already exists".to_string())]);
+
+        let written = edit(&STYLE, &files, &Edit::Move {
+            from: KeyPath::parse("a").unwrap(),
+            to: KeyPath::parse("b").unwrap(),
+            force: true,
+        });
+        assert!(written.is_ok());
+        assert_eq!(fs::read_to_string(files[0].path()).unwrap(), "{\n  \"b\": \"1\"\n}");
+    }
+
+    #[test]
+    fn insert_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = files(dir.path(), &["{\"b\": \"1\"}", "{}"]);
+        let paths = vec![KeyPath::parse("b").unwrap(), KeyPath::parse("a.c").unwrap()];
+
+        assert!(edit(&STYLE, &files, &Edit::Insert(paths, "".to_string())).is_ok());
+        assert_eq!(
+            fs::read_to_string(files[0].path()).unwrap(),
+            "{\n  \"a\": {\n    \"c\": \"\"\n  },\n  \"b\": \"1\"\n}",
+        );
+        assert_eq!(
+            fs::read_to_string(files[1].path()).unwrap(),
+            "{\n  \"a\": {\n    \"c\": \"\"\n  },\n  \"b\": \"\"\n}",
+        );
+    }
+
+    #[test]
+    fn get_values() {
+        let files = [
+            LoadedFile::new(PathBuf::from("de.json"), "{\"a\": {\"b\": \"1\"}}".to_string()),
+            LoadedFile::new(PathBuf::from("en.json"), "{}".to_string()),
+        ];
+
+        assert_eq!(get(&STYLE, &files, &KeyPath::parse("a").unwrap()), vec![
+            (PathBuf::from("de.json"), Ok(Some("{\n  \"b\": \"1\"\n}".to_string()))),
+            (PathBuf::from("en.json"), Ok(None)),
+        ]);
+    }
+}
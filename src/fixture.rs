@@ -0,0 +1,19 @@
+//! Files shared by the tests of the library and of the command line tool.
+
+use std::path::PathBuf;
+use jsoncpl::io::LoadedFile;
+use jsoncpl::{JsonObject, JsonStyle, Parser};
+
+/// Files with the given paths and contents.
+pub fn files(files: &[(&str, &str)]) -> Vec<LoadedFile> {
+    files.iter()
+        .map(|(path, content)| LoadedFile::new(PathBuf::from(path), content.to_string()))
+        .collect()
+}
+
+/// The files parsed without checking their style, as the checks take them.
+pub fn parsed(files: &[LoadedFile]) -> Vec<(&LoadedFile, JsonObject<'_>)> {
+    files.iter()
+        .map(|file| (file, Parser::new(JsonStyle::IGNORE).parse(file.content()).unwrap().0))
+        .collect()
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Can not run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lists the files of the git repository containing `dir` which changed since the revision `rev`,
+/// including uncommitted and untracked files. The paths are canonical, deleted files are included.
+pub fn changed_files(dir: &Path, rev: &str) -> Result<HashSet<PathBuf>, String> {
+    let root = PathBuf::from(git(dir, &["rev-parse", "--show-toplevel"])?.trim())
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+    // -z keeps git from quoting paths with unusual characters
+    let changed = git(&root, &["diff", "--name-only", "-z", "--no-renames", rev, "--"])?;
+    let untracked = git(&root, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+
+    Ok(changed.split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|path| !path.is_empty())
+        .map(|path| root.join(path))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use super::*;
+
+    fn run(dir: &Path, args: &[&str]) {
+        git(dir, &[&["-c", "user.name=jsoncpl", "-c", "user.email=jsoncpl@localhost"], args].concat()).unwrap();
+    }
+
+    #[test]
+    fn changed_since_revision() {
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("de")).unwrap();
+        fs::create_dir_all(root.join("en")).unwrap();
+        fs::write(root.join("de/common.json"), "{}").unwrap();
+        fs::write(root.join("en/common.json"), "{}").unwrap();
+        fs::write(root.join("en/main.json"), "{}").unwrap();
+        run(&root, &["init", "-q"]);
+        run(&root, &["add", "-A"]);
+        run(&root, &["commit", "-q", "-m", "init"]);
+
+        fs::write(root.join("en/common.json"), "{\"a\": \"b\"}").unwrap();
+        fs::remove_file(root.join("en/main.json")).unwrap();
+        fs::write(root.join("de/main.json"), "{}").unwrap();
+        fs::write(root.join("de/größe.json"), "{}").unwrap();
+
+        let changed = changed_files(&root.join("de"), "HEAD").unwrap();
+        assert_eq!(changed, HashSet::from([
+            root.join("en/common.json"),
+            root.join("en/main.json"),
+            root.join("de/main.json"),
+            root.join("de/größe.json"),
+        ]));
+    }
+
+    #[test]
+    fn unknown_revision() {
+        let repo = tempfile::tempdir().unwrap();
+        run(repo.path(), &["init", "-q"]);
+
+        assert!(changed_files(repo.path(), "does-not-exist").is_err());
+    }
+}
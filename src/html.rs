@@ -0,0 +1,261 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use jsoncpl::checks::suppress::DIRECTIVE;
+use jsoncpl::diagnostic::{Diagnostic, Location};
+use jsoncpl::io::LoadedFile;
+use jsoncpl::lint::{GroupReport, Report};
+use jsoncpl::parser::model::{JsonObject, JsonStyle, JsonType};
+use jsoncpl::parser::parser::Parser;
+use jsoncpl::stats::LocaleStats;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse;margin-bottom:2em}\
+th,td{border:1px solid #ccc;padding:.25em .5em;text-align:left}\
+td.missing{background:#f8d7da}td.mismatch{background:#fff3cd}td.invalid{background:#e2e3e5}\
+.bar{background:#eee;width:10em;height:.8em}.bar div{background:#4caf50;height:100%}";
+
+/// The state of a key in the file of a folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cell {
+    Present(Location),
+    Missing,
+    /// The key or one of its parents is a string in one file and an object in another
+    Mismatch(Location),
+    /// The file can not be parsed
+    Invalid,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn same_start(a: &Location, b: &Location) -> bool {
+    a.file == b.file && a.start_line == b.start_line && a.column == b.column
+}
+
+/// Collects the location of the value of every key, objects included.
+fn nodes(file: &LoadedFile, object: &JsonObject, path: &[String], found: &mut BTreeMap<Vec<String>, (Location, bool)>) {
+    for (key, value) in object.values.iter().filter(|(key, _)| key.value != DIRECTIVE) {
+        let mut path = path.to_vec();
+        path.push(key.value.to_string());
+        match value {
+            JsonType::Object(child) => {
+                found.insert(path.clone(), (Location::span(file.path(), &child.start, &child.end), false));
+                nodes(file, child, &path, found);
+            }
+            JsonType::String(string) => {
+                found.insert(path, (Location::span(file.path(), &string.start, &string.end), true));
+            }
+        }
+    }
+}
+
+/// The keys of the group and the state of every key in the file of every folder.
+fn matrix(folders: &[PathBuf], group: &GroupReport) -> Vec<(Vec<String>, Vec<Cell>)> {
+    let files = folders.iter()
+        .map(|folder| {
+            let file = group.files.iter().find(|file| file.path() == &folder.join(&group.path))?;
+            let mut found = BTreeMap::new();
+            let parsed = Parser::new(JsonStyle::IGNORE).parse(file.content())
+                .map(|(json, _)| nodes(file, &json, &[], &mut found))
+                .is_ok();
+
+            Some((file.path().clone(), parsed.then_some(found)))
+        })
+        .collect::<Vec<_>>();
+    let keys = files.iter()
+        .flatten()
+        .filter_map(|(_, found)| found.as_ref())
+        .flat_map(|found| found.iter().filter(|(_, (_, leaf))| *leaf).map(|(key, _)| key.clone()))
+        .collect::<BTreeSet<_>>();
+    let mismatches = group.diagnostics.iter()
+        .filter_map(|diagnostic| match diagnostic {
+            Diagnostic::TypeMismatch { left, right, .. } => Some([left, right]),
+            _ => None,
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    keys.into_iter()
+        .map(|key| {
+            let cells = files.iter()
+                .map(|file| {
+                    let Some((path, found)) = file else {
+                        return Cell::Missing;
+                    };
+                    let Some(found) = found else {
+                        return Cell::Invalid;
+                    };
+                    let missing = group.diagnostics.iter().any(|diagnostic| matches!(
+                        diagnostic,
+                        Diagnostic::MissingKey { file, key: missing } if file == path && key.starts_with(missing)
+                    ));
+                    if missing {
+                        return Cell::Missing;
+                    }
+                    let mismatch = (1..=key.len())
+                        .filter_map(|length| found.get(&key[..length]))
+                        .find(|(location, _)| mismatches.iter().any(|mismatch| same_start(mismatch, location)));
+                    match (mismatch, found.get(&key)) {
+                        (Some((location, _)), _) => Cell::Mismatch(location.clone()),
+                        (None, Some((location, true))) => Cell::Present(location.clone()),
+                        _ => Cell::Missing,
+                    }
+                })
+                .collect();
+
+            (key, cells)
+        })
+        .collect()
+}
+
+fn link(base: &Path, location: &Location, text: &str) -> String {
+    let file = match base.is_absolute() {
+        true => std::path::absolute(&location.file).unwrap_or_else(|_| location.file.clone()),
+        false => location.file.clone(),
+    };
+    let href = pathdiff::diff_paths(&file, base).unwrap_or(file);
+    format!(
+        "<a href=\"{}#L{}\" title=\"{}:{}:{}\">{}</a>",
+        escape(&href.to_string_lossy()),
+        location.start_line,
+        escape(&location.file.to_string_lossy()),
+        location.start_line,
+        location.column,
+        text,
+    )
+}
+
+/// Renders a self-contained HTML page with the completeness of every folder and, for every group of the
+/// `report`, a matrix of its keys against the folders highlighting missing keys and type mismatches. The
+/// links to the files are relative to `base`, the directory of the page.
+pub fn coverage(report: &Report, locales: &[LocaleStats], base: &Path) -> String {
+    let folders = locales.iter().map(|locale| locale.folder.clone()).collect::<Vec<_>>();
+    let mut html = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        "<title>Translation Coverage</title>".to_string(),
+        format!("<style>{}</style>", STYLE),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        "<h1>Translation Coverage</h1>".to_string(),
+        "<table>".to_string(),
+        "<tr><th>Folder</th><th>Keys</th><th>Missing</th><th>Untranslated</th><th>Empty</th><th>Obsolete</th><th>Complete</th></tr>".to_string(),
+    ];
+    for locale in locales {
+        let counts = &locale.counts;
+        html.push(format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><div class=\"bar\"><div style=\"width:{:.1}%\"></div></div>{:.1}%</td></tr>",
+            escape(&locale.folder.to_string_lossy()),
+            counts.total,
+            counts.missing,
+            counts.untranslated,
+            counts.empty,
+            counts.obsolete,
+            locale.complete,
+            locale.complete,
+        ));
+    }
+    html.push("</table>".to_string());
+
+    let header = folders.iter()
+        .map(|folder| format!("<th>{}</th>", escape(&folder.to_string_lossy())))
+        .collect::<String>();
+    for group in &report.groups {
+        html.push(format!("<h2>{}</h2>", escape(&group.path.to_string_lossy())));
+        html.push("<table>".to_string());
+        html.push(format!("<tr><th>Key</th>{}</tr>", header));
+        for (key, cells) in matrix(&folders, group) {
+            let cells = cells.iter()
+                .map(|cell| match cell {
+                    Cell::Present(location) => format!("<td>{}</td>", link(base, location, &format!("line {}", location.start_line))),
+                    Cell::Missing => "<td class=\"missing\">missing</td>".to_string(),
+                    Cell::Mismatch(location) => format!("<td class=\"mismatch\">{}</td>", link(base, location, "type mismatch")),
+                    Cell::Invalid => "<td class=\"invalid\">invalid json</td>".to_string(),
+                })
+                .collect::<String>();
+            html.push(format!("<tr><td>{}</td>{}</tr>", escape(&key.join(".")), cells));
+        }
+        html.push("</table>".to_string());
+    }
+    html.push("</body>".to_string());
+    html.push("</html>".to_string());
+
+    html.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixture;
+    use super::*;
+
+    fn group(files: &[(&str, &str)]) -> GroupReport {
+        let files = fixture::files(files);
+        let jsons = fixture::parsed(&files);
+        let mut diagnostics = Vec::new();
+        jsoncpl::checks::entry_parity::entry_parity(&jsons, &Default::default(), &mut diagnostics);
+
+        GroupReport {
+            path: PathBuf::from("a.json"),
+            diagnostics,
+            files,
+        }
+    }
+
+    #[test]
+    fn missing_and_mismatched_cells() {
+        let group = group(&[
+            ("en/a.json", "{\"a\": \"A\", \"b\": \"B\", \"c\": {\"d\": \"D\"}}"),
+            ("de/a.json", "{\"a\": \"A\", \"c\": {\"d\": \"D\"}}"),
+            ("fr/a.json", "{\"a\": {\"e\": \"E\"}, \"b\": \"B\", \"c\": {\"d\": \"D\"}}"),
+        ]);
+        let folders = ["en", "de", "fr", "ja"].map(PathBuf::from);
+        let states = matrix(&folders, &group).into_iter()
+            .map(|(key, cells)| {
+                let states = cells.iter()
+                    .map(|cell| match cell {
+                        Cell::Present(_) => 'p',
+                        Cell::Missing => 'm',
+                        Cell::Mismatch(_) => 't',
+                        Cell::Invalid => 'i',
+                    })
+                    .collect::<String>();
+                (key.join("."), states)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(states, vec![
+            ("a".to_string(), "tptm".to_string()),
+            ("a.e".to_string(), "tmtm".to_string()),
+            ("b".to_string(), "pmpm".to_string()),
+            ("c.d".to_string(), "pppm".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn escape_keys_and_link_lines() {
+        let group = group(&[("en/a.json", "{\n\"<b>\": \"B\"\n}")]);
+        let report = Report {
+            diagnostics: Vec::new(),
+            groups: vec![group],
+            untranslated: Vec::new(),
+            severities: Default::default(),
+            cache_error: None,
+        };
+        let html = coverage(&report, &[LocaleStats {
+            folder: PathBuf::from("en"),
+            counts: Default::default(),
+            complete: 100.0,
+            files: Vec::new(),
+        }], Path::new("."));
+
+        assert!(html.contains("<h2>a.json</h2>"));
+        assert!(!html.contains("<b>"));
+        assert!(html.contains("<td>&lt;b&gt;</td><td><a href=\"en/a.json#L2\" title=\"en/a.json:2:9\">line 2</a></td>"));
+    }
+}
@@ -1,7 +1,8 @@
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+#[allow(clippy::ptr_arg, clippy::only_used_in_recursion, clippy::expect_fun_call, clippy::explicit_auto_deref)]
 fn file_tree(
     base_path: &PathBuf,
     path: &PathBuf,
@@ -13,10 +14,11 @@ fn file_tree(
                 path.as_os_str().to_str().unwrap()
         )
     );
-    for entry in directory {
-        let entry = entry.unwrap();
-        let path = entry.path();
-
+    let mut paths = directory
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    paths.sort();
+    for path in paths {
         if path.is_dir() {
             files = file_tree(base_path, &path, files);
         } else {
@@ -28,7 +30,7 @@ fn file_tree(
 }
 
 pub fn read_folders(
-    folders: &Vec<PathBuf>
+    folders: &[PathBuf]
 ) -> Vec<(PathBuf, Vec<File>)> {
     let mut lang_content: Vec<(PathBuf, Vec<File>)> = Vec::new();
     for path in folders {
@@ -43,6 +45,25 @@ pub fn read_folders(
     lang_content
 }
 
+/// Collects the file at `relative_path` of every folder, folders without that file yield no files.
+pub fn read_file_in_folders(
+    folders: &[PathBuf],
+    relative_path: &Path,
+) -> Vec<(PathBuf, Vec<File>)> {
+    folders.iter()
+        .map(|folder| {
+            let path = folder.join(relative_path);
+            let files = if path.is_file() {
+                vec![File(path)]
+            } else {
+                Vec::new()
+            };
+
+            (folder.clone(), files)
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct File(PathBuf);
 pub struct LoadedFile(PathBuf, String);
@@ -58,6 +79,10 @@ impl File {
 }
 
 impl LoadedFile {
+    pub fn new(path: PathBuf, content: String) -> LoadedFile {
+        LoadedFile(path, content)
+    }
+
     pub fn content(&self) -> &String {
         &self.1
     }
@@ -0,0 +1,33 @@
+//! Linting, formatting and comparison of dictionary-like json files, as used for translations.
+//!
+//! The [`Parser`] reads a json file and reports where it deviates from a [`JsonStyle`], the [`Generator`]
+//! writes a json file in that style. The [`checks`] compare the files of multiple folders and report their
+//! findings as [`Diagnostic`]s, [`lint::lint`] runs all of them.
+
+pub mod parser;
+pub mod checks;
+pub mod diagnostic;
+pub mod io;
+pub mod lint;
+pub mod edit;
+pub mod usage;
+pub mod stats;
+pub mod baseline;
+pub mod severity;
+pub mod config;
+mod util;
+mod cache;
+mod natural_sort;
+mod collation;
+#[cfg(test)]
+extern crate self as jsoncpl;
+#[cfg(test)]
+mod fixture;
+
+pub use diagnostic::{Diagnostic, Location};
+pub use parser::error::{StyleError, StyleErrorKind};
+pub use parser::document::{JsonDocument, JsonValue};
+pub use parser::generator::Generator;
+pub use parser::model::{JsonObject, JsonString, JsonStyle, JsonType, LineEnding, SortAlgorithm, SortOrder, Span};
+pub use parser::parser::Parser;
+pub use parser::path::{EditError, KeyPath};
@@ -0,0 +1,455 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use colored::Colorize;
+use rayon::prelude::*;
+use crate::cache::{Cache, FileEntry, GroupEntry};
+use crate::checks::entry_parity::entry_parity;
+use crate::checks::file_parity::file_parity;
+use crate::checks::file_style::file_style;
+use crate::checks::suppress::Suppressions;
+use crate::checks::length::{length, LengthRule};
+use crate::checks::undefined::undefined;
+use crate::checks::untranslated::untranslated;
+use crate::checks::unused;
+use crate::checks::values::{values, ValueChecks};
+use crate::diagnostic::Diagnostic;
+use crate::io::{File, LoadedFile};
+use crate::parser::document::JsonDocument;
+use crate::parser::generator::Generator;
+use crate::parser::model::{JsonObject, JsonStyle};
+use crate::parser::naming::{rename, KeyNaming};
+use crate::parser::parser::Parser;
+use crate::severity::{Severities, Severity};
+use crate::usage::Usages;
+use crate::util;
+
+#[derive(Default)]
+pub struct LintOptions {
+    /// The number of threads used to lint the groups, 0 uses one per cpu
+    pub jobs: usize,
+    /// Caches the results in this directory and only checks files which changed since the last run
+    pub cache_dir: Option<PathBuf>,
+    /// Only lints the groups containing one of these canonical paths
+    pub changed: Option<HashSet<PathBuf>>,
+    /// Reports values identical to the value in the first folder, except for these allowed values
+    pub untranslated: Option<Vec<String>>,
+    /// The checks applied to every string value
+    pub values: ValueChecks,
+    /// The naming conventions of the keys
+    pub naming: KeyNaming,
+    /// The length limits of the values
+    pub lengths: Vec<LengthRule>,
+    /// The rules disabled for some keys, the directives of the files are added to them
+    pub suppressions: Suppressions,
+    /// The severity of the rules, diagnostics of rules which are off are dropped
+    pub severities: Severities,
+}
+
+/// The files of a `file_parity` group and their diagnostics.
+pub struct GroupReport {
+    /// The path of the files relative to their folder
+    pub path: PathBuf,
+    pub files: Vec<LoadedFile>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+pub struct Report {
+    /// The diagnostics concerning the folders, like missing files
+    pub diagnostics: Vec<Diagnostic>,
+    /// The groups ordered by their relative path
+    pub groups: Vec<GroupReport>,
+    /// The number of untranslated values of every folder, if they were checked
+    pub untranslated: Vec<(PathBuf, usize)>,
+    pub severities: Severities,
+    /// Why the cache could not be written, the report itself is complete
+    pub cache_error: Option<String>,
+}
+
+impl Report {
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter()
+            .chain(self.groups.iter().flat_map(|group| group.diagnostics.iter()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics().next().is_none()
+    }
+
+    /// The number of diagnostics with the severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.diagnostics()
+            .filter(|diagnostic| self.severities.of(diagnostic) == severity)
+            .count()
+    }
+
+    /// Counts the untranslated values of the folders in `untranslated` again, e.g. after diagnostics were removed.
+    pub fn count_untranslated(&mut self) {
+        for (root, count) in &mut self.untranslated {
+            *count = self.groups.iter()
+                .flat_map(|group| &group.diagnostics)
+                .filter(|diagnostic| matches!(
+                    diagnostic,
+                    Diagnostic::Untranslated { location, .. } if location.file.starts_with(&*root)
+                ))
+                .count();
+        }
+    }
+
+    /// Drops the diagnostics of the rules which are off.
+    fn retain_enabled(&mut self) {
+        let severities = &self.severities;
+        self.diagnostics.retain(|diagnostic| severities.of(diagnostic) != Severity::Off);
+        for group in &mut self.groups {
+            group.diagnostics.retain(|diagnostic| severities.of(diagnostic) != Severity::Off);
+        }
+    }
+
+    /// Renders every diagnostic for the terminal, warnings are marked as such.
+    pub fn render(&self, style: &JsonStyle) -> Vec<String> {
+        let render = |diagnostic: &Diagnostic, files: &[LoadedFile]| match self.severities.of(diagnostic) {
+            Severity::Warn => format!("[{}] {}", "WARNING".yellow(), diagnostic.render(style, files)),
+            _ => diagnostic.render(style, files),
+        };
+
+        self.diagnostics.iter()
+            .map(|diagnostic| render(diagnostic, &[]))
+            .chain(self.groups.iter().flat_map(|group| {
+                group.diagnostics.iter().map(|diagnostic| render(diagnostic, &group.files))
+            }))
+            .collect()
+    }
+}
+
+fn lint_group(
+    style: &JsonStyle,
+    options: &LintOptions,
+    cache: &Cache,
+    (path, file_type): (PathBuf, Vec<File>),
+) -> (GroupReport, Cache) {
+    let mut fresh = Cache::default();
+    let mut errors = Vec::new();
+    let loaded_files = file_type.into_iter()
+        .map(|file| file.load())
+        .collect::<Vec<_>>();
+    let style_key = format!(
+        "{:?}{:?}{:?}{:?}{:?}{:?}",
+        style, options.untranslated, options.values, options.naming, options.lengths, options.suppressions,
+    );
+    let parser = Parser::new(*style).with_naming(options.naming.clone());
+    let members = loaded_files.iter()
+        .map(|file| (file.path().clone(), util::hash(&[&style_key, file.content()])))
+        .collect::<Vec<_>>();
+
+    if let Some(group) = cache.group(&path, &members) {
+        // Nothing changed since the last run
+        for (file, key) in &members {
+            if let Some(entry) = cache.file(file, *key) {
+                errors.extend(entry.diagnostics.iter().cloned());
+                fresh.insert_file(file.clone(), entry.clone());
+            }
+        }
+        errors.extend(group.diagnostics.iter().cloned());
+        fresh.insert_group(path.clone(), group.clone());
+    } else {
+        let mut jsons = Vec::new();
+        let mut suppressions = options.suppressions.clone();
+        for (file, (file_path, key)) in loaded_files.iter().zip(&members) {
+            let json = if let Some(entry) = cache.file(file_path, *key) {
+                errors.extend(entry.diagnostics.iter().cloned());
+                fresh.insert_file(file_path.clone(), entry.clone());
+                if entry.parsed {
+                    Parser::new(*style).parse(file.content()).ok().map(|(mut json, _)| {
+                        suppressions.extract(&mut json);
+                        json
+                    })
+                } else {
+                    None
+                }
+            } else {
+                let mut file_errors = Vec::new();
+                let mut json = file_style(&parser, file, &options.suppressions, &mut file_errors);
+                if let Some(json) = &mut json {
+                    // The value checks are disabled by the directives of the file like the style checks
+                    let mut own = options.suppressions.clone();
+                    own.collect(json);
+                    suppressions.extract(json);
+                    values(file, json, &options.values, &mut file_errors);
+                    file_errors.retain(|error| !own.suppresses(error));
+                }
+                errors.extend(file_errors.iter().cloned());
+                fresh.insert_file(file_path.clone(), FileEntry {
+                    key: *key,
+                    parsed: json.is_some(),
+                    diagnostics: file_errors,
+                });
+
+                json
+            };
+            if let Some(json) = json {
+                jsons.push((file, json));
+            }
+        }
+
+        let mut parity_errors = Vec::new();
+        entry_parity(&jsons, &suppressions, &mut parity_errors);
+        if let Some(allowlist) = &options.untranslated {
+            untranslated(&jsons, allowlist, &mut parity_errors);
+        }
+        length(&jsons, &options.lengths, &mut parity_errors);
+        parity_errors.retain(|error| !suppressions.suppresses(error));
+        errors.extend(parity_errors.iter().cloned());
+        fresh.insert_group(path.clone(), GroupEntry {
+            members,
+            diagnostics: parity_errors,
+        });
+    }
+
+    (GroupReport {
+        path,
+        files: loaded_files,
+        diagnostics: errors,
+    }, fresh)
+}
+
+/// Only keeps the groups containing at least one of the `changed` files, the diagnostics of
+/// missing files are kept for the kept groups.
+fn retain_changed(
+    roots: &[PathBuf],
+    changed: &HashSet<PathBuf>,
+    file_types: &mut Vec<(PathBuf, Vec<File>)>,
+    parity_errors: &mut Vec<Diagnostic>,
+) {
+    let roots = roots.iter()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect::<Vec<_>>();
+    file_types.retain(|(path, _)| roots.iter().any(|root| changed.contains(&root.join(path))));
+    parity_errors.retain(|error| match error {
+        Diagnostic::NotFound { file, .. } => file_types.iter().any(|(path, _)| path == file),
+        _ => true,
+    });
+}
+
+/// Checks the style of every file and compares the files of every `file_parity` group.
+pub fn lint(
+    style: &JsonStyle,
+    folders: Vec<(PathBuf, Vec<File>)>,
+    options: &LintOptions,
+) -> Report {
+    let roots = folders.iter().map(|(root, _)| root.clone()).collect::<Vec<_>>();
+    let mut parity_errors = Vec::new();
+    let mut file_types = file_parity(folders, &mut parity_errors);
+    if let Some(changed) = &options.changed {
+        retain_changed(&roots, changed, &mut file_types, &mut parity_errors);
+    }
+
+    let cache = options.cache_dir.as_ref()
+        .map(|cache_dir| Cache::load(cache_dir))
+        .unwrap_or_default();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.jobs)
+        .build()
+        .unwrap();
+    let mut results = pool.install(|| {
+        file_types.into_par_iter()
+            .map(|file_type| lint_group(style, options, &cache, file_type))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by(|(a, _), (b, _)| a.path.cmp(&b.path));
+    // Only the entries of this run are kept, the entries of renamed or deleted files are dropped
+    let mut touched = Cache::default();
+    let mut groups = Vec::new();
+    for (group, fresh) in results {
+        groups.push(group);
+        touched.extend(fresh);
+    }
+
+    let cache_error = options.cache_dir.as_ref()
+        .and_then(|cache_dir| touched.store(cache_dir).err())
+        .map(|e| e.to_string());
+    let mut report = Report {
+        diagnostics: parity_errors,
+        groups,
+        untranslated: Vec::new(),
+        severities: options.severities.clone(),
+        cache_error,
+    };
+    report.retain_enabled();
+    if options.untranslated.is_some() {
+        report.untranslated = roots.into_iter().map(|root| (root, 0)).collect();
+        report.count_untranslated();
+    }
+
+    report
+}
+
+/// Reports the keys of the files of `folders` which are never used according to `usages`.
+pub fn unused(folders: Vec<(PathBuf, Vec<File>)>, usages: &Usages, dynamic: bool) -> Report {
+    // Missing files are the concern of `lint`
+    let file_types = file_parity(folders, &mut Vec::new());
+    let groups = file_types.into_iter()
+        .map(|(path, files)| {
+            let files = files.into_iter().map(File::load).collect::<Vec<_>>();
+            let mut errors = Vec::new();
+            let mut jsons = Vec::new();
+            for file in &files {
+                match Parser::new(JsonStyle::IGNORE).parse(file.content()) {
+                    Ok((mut json, _)) => {
+                        Suppressions::strip(&mut json);
+                        jsons.push((file, json));
+                    }
+                    Err(e) => errors.push(Diagnostic::Parse {
+                        file: file.path().clone(),
+                        message: format!("{}", e),
+                    }),
+                }
+            }
+            unused::unused(&path, &jsons, usages, dynamic, &mut errors);
+
+            GroupReport {
+                path,
+                files,
+                diagnostics: errors,
+            }
+        })
+        .collect();
+
+    Report {
+        diagnostics: Vec::new(),
+        groups,
+        untranslated: Vec::new(),
+        severities: Severities::default(),
+        cache_error: None,
+    }
+}
+
+/// Reports the keys used according to `usages` which are missing in the `reference` file and in the
+/// other files of its folder, `siblings`.
+pub fn undefined_keys(reference: &LoadedFile, siblings: &[LoadedFile], usages: &Usages) -> Vec<Diagnostic> {
+    fn parse<'a>(file: &'a LoadedFile, errors: &mut Vec<Diagnostic>) -> Option<JsonObject<'a>> {
+        match Parser::new(JsonStyle::IGNORE).parse(file.content()) {
+            Ok((json, _)) => Some(json),
+            Err(e) => {
+                errors.push(Diagnostic::Parse {
+                    file: file.path().clone(),
+                    message: format!("{}", e),
+                });
+                None
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    let siblings = siblings.iter()
+        .filter_map(|sibling| parse(sibling, &mut errors))
+        .collect::<Vec<_>>();
+    if let Some(json) = parse(reference, &mut errors) {
+        undefined(reference, &json, &siblings, usages, &mut errors);
+    }
+
+    errors
+}
+
+/// Formats a single file, files which can not be parsed are left untouched.
+pub fn format_file(style: &JsonStyle, path: &Path, content: &str) -> Result<String, Diagnostic> {
+    fix_file(style, &KeyNaming::default(), path, content)
+}
+
+/// Formats a single file and renames the keys which do not follow the naming conventions.
+pub fn fix_file(style: &JsonStyle, naming: &KeyNaming, path: &Path, content: &str) -> Result<String, Diagnostic> {
+    match Parser::new(JsonStyle::IGNORE).parse(content) {
+        Ok((parsed, _)) => {
+            let mut document = JsonDocument::from(parsed);
+            naming.fix(&mut document);
+
+            Ok(Generator::new(*style).generate(document))
+        }
+        Err(e) => Err(Diagnostic::Parse {
+            file: path.to_path_buf(),
+            message: format!("{}", e),
+        }),
+    }
+}
+
+/// Formats the files of `folders` in place and returns the files which could not be parsed.
+pub fn format(style: &JsonStyle, folders: Vec<(PathBuf, Vec<File>)>) -> Vec<Diagnostic> {
+    let mut errors = Vec::new();
+    for (_, files) in folders {
+        for file in files {
+            let file = file.load();
+            match format_file(style, file.path(), file.content()) {
+                Ok(generated) => {
+                    if generated != *file.content() {
+                        fs::write(file.path(), &generated).unwrap();
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+    }
+
+    errors
+}
+
+/// Renames the keys of the files of `folders` which do not follow the naming conventions, the renames are
+/// decided for every `file_parity` group at once. Only the files with renamed keys are written, in the style.
+/// Returns the files which could not be parsed.
+pub fn fix(style: &JsonStyle, naming: &KeyNaming, folders: Vec<(PathBuf, Vec<File>)>) -> Vec<Diagnostic> {
+    let mut errors = Vec::new();
+    // Missing files are the concern of `lint`
+    for (_, files) in file_parity(folders, &mut Vec::new()) {
+        let files = files.into_iter().map(File::load).collect::<Vec<_>>();
+        let mut documents = Vec::new();
+        for file in &files {
+            match Parser::new(JsonStyle::IGNORE).parse(file.content()) {
+                Ok((parsed, _)) => documents.push((file, JsonDocument::from(parsed))),
+                Err(e) => errors.push(Diagnostic::Parse {
+                    file: file.path().clone(),
+                    message: format!("{}", e),
+                }),
+            }
+        }
+        let renames = naming.renames(&documents.iter().map(|(_, document)| document).collect::<Vec<_>>());
+        for (file, mut document) in documents {
+            if rename(&renames, &mut document) {
+                let generated = Generator::new(*style).generate(document);
+                if generated != *file.content() {
+                    fs::write(file.path(), &generated).unwrap();
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod test {
+    use crate::io::read_folders;
+    use super::*;
+
+    #[test]
+    fn cache_drops_deleted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let folders = ["de", "en"].map(|folder| dir.path().join(folder));
+        for folder in &folders {
+            fs::create_dir(folder).unwrap();
+            fs::write(folder.join("common.json"), "{\"a\": \"A\"}").unwrap();
+            fs::write(folder.join("old.json"), "{\"b\": \"B\"}").unwrap();
+        }
+        let options = LintOptions {
+            cache_dir: Some(dir.path().join("cache")),
+            ..Default::default()
+        };
+        let cached = || fs::read_to_string(dir.path().join("cache/cache.json")).unwrap();
+
+        lint(&JsonStyle::IGNORE, read_folders(&folders), &options);
+        assert!(cached().contains("old.json"));
+        for folder in &folders {
+            fs::remove_file(folder.join("old.json")).unwrap();
+        }
+        lint(&JsonStyle::IGNORE, read_folders(&folders), &options);
+        assert!(!cached().contains("old.json"));
+        assert!(cached().contains("common.json"));
+    }
+}
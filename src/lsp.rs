@@ -0,0 +1,675 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{CodeActionRequest, Formatting, GotoDefinition, Request as _};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability,
+    Diagnostic as LspDiagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, GotoDefinitionParams, Location, OneOf, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextEdit, Url, WorkspaceEdit,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use jsoncpl::checks::entry_parity::entry_parity;
+use jsoncpl::checks::suppress::DIRECTIVE;
+use jsoncpl::checks::suppress::{check_directives, Suppressions};
+use jsoncpl::diagnostic::Diagnostic;
+use jsoncpl::io::LoadedFile;
+use jsoncpl::parser::document::{JsonDocument, JsonValue};
+use jsoncpl::parser::generator::Generator;
+use jsoncpl::parser::model::{JsonObject, JsonString, JsonStyle, JsonType, SortAlgorithm};
+use jsoncpl::parser::parser::Parser;
+use jsoncpl::severity::{Severities, Severity};
+
+type LspResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Runs the language server over stdio until the client shuts it down, `suppressions` and `severities` select
+/// the reported rules like for `lint`.
+pub fn run(style: JsonStyle, folders: &[PathBuf], suppressions: Suppressions, severities: Severities) -> LspResult<()> {
+    // Messages are shown by the editor, terminal colors would end up as garbage
+    colored::control::set_override(false);
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    })?;
+    connection.initialize(capabilities)?;
+    Server::new(style, folders, suppressions, severities).serve(&connection)?;
+    // The writer thread only stops once the connection is gone
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
+
+/// Converts a byte offset into `text` to a position, which counts utf-16 code units.
+fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// Converts a position to a byte offset into `text`, positions past the end of a line are clamped.
+fn offset(text: &str, position: Position) -> usize {
+    let mut offset = 0;
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        if i as u32 == position.line {
+            let line = line.trim_end_matches(['\r', '\n']);
+            let mut units = 0;
+            for (j, c) in line.char_indices() {
+                if units >= position.character {
+                    return offset + j;
+                }
+                units += c.len_utf16() as u32;
+            }
+
+            return offset + line.len();
+        }
+        offset += line.len();
+    }
+
+    text.len()
+}
+
+/// Converts a 1-based line and byte column as found in a `Location` to a byte offset into `text`.
+fn line_column_offset(text: &str, line: u32, column: usize) -> usize {
+    let line_start = text.split_inclusive('\n')
+        .take(line.saturating_sub(1) as usize)
+        .map(|line| line.len())
+        .sum::<usize>();
+
+    (line_start + column.saturating_sub(1)).min(text.len())
+}
+
+/// The range of the character at `offset`, or an empty range at the end of the text.
+fn char_range(text: &str, offset: usize) -> Range {
+    let end = text[offset..].chars().next().map(|c| offset + c.len_utf8()).unwrap_or(offset);
+
+    Range::new(position(text, offset), position(text, end))
+}
+
+/// The range of a key including its quotes.
+fn key_range(text: &str, key: &JsonString) -> Range {
+    Range::new(
+        position(text, key.start.location_offset() - 1),
+        position(text, key.end.location_offset()),
+    )
+}
+
+fn full_range(text: &str) -> Range {
+    Range::new(Position::new(0, 0), position(text, text.len()))
+}
+
+/// The edit replacing only the part between the common prefix and the common suffix of `old` and `new`.
+fn minimal_edit(old: &str, new: &str) -> TextEdit {
+    let prefix = old.char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((i, _), _)| i)
+        .unwrap_or(old.len().min(new.len()));
+    let suffix = old[prefix..].chars().rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .sum::<usize>();
+
+    TextEdit::new(
+        Range::new(position(old, prefix), position(old, old.len() - suffix)),
+        new[prefix..new.len() - suffix].to_string(),
+    )
+}
+
+/// Adds the entries of `sibling` missing in `document`, their strings are left empty so no text of
+/// another language ends up in the file.
+fn insert_missing(document: &mut JsonDocument, sibling: &JsonDocument) {
+    fn placeholder(value: &JsonValue) -> JsonValue {
+        match value {
+            JsonValue::Object(object) => JsonValue::Object(JsonDocument {
+                values: object.values.iter().map(|(key, value)| (key.clone(), placeholder(value))).collect(),
+            }),
+            JsonValue::String(_) => JsonValue::String(String::new()),
+        }
+    }
+
+    for (key, value) in sibling.values.iter().filter(|(key, _)| key != DIRECTIVE) {
+        match document.values.iter_mut().find(|(existing, _)| existing == key) {
+            Some((_, JsonValue::Object(object))) => if let JsonValue::Object(value) = value {
+                insert_missing(object, value);
+            },
+            Some(_) => {}
+            None => document.values.push((key.clone(), placeholder(value))),
+        }
+    }
+}
+
+/// Follows `path` through `object` and returns the keys which exist along the way.
+fn lookup<'a>(object: &JsonObject<'a>, path: &[String]) -> Vec<JsonString<'a>> {
+    let mut found = Vec::new();
+    let mut object = object;
+    for segment in path {
+        match object.values.iter().find(|(key, _)| key.value == segment) {
+            Some((key, value)) => {
+                found.push(*key);
+                match value {
+                    JsonType::Object(child) => object = child,
+                    JsonType::String(_) => break,
+                }
+            }
+            None => break,
+        }
+    }
+
+    found
+}
+
+/// Finds the path of the key whose key or string value contains `offset`.
+fn key_at(object: &JsonObject, offset: usize) -> Option<Vec<String>> {
+    for (key, value) in &object.values {
+        let (value_start, value_end) = match value {
+            JsonType::Object(child) => (child.start.location_offset(), child.end.location_offset()),
+            JsonType::String(string) => (string.start.location_offset() - 1, string.end.location_offset()),
+        };
+        if key.start.location_offset() - 1 <= offset && offset < key.end.location_offset() {
+            return Some(vec![key.value.to_string()]);
+        }
+        if value_start <= offset && offset < value_end {
+            return match value {
+                JsonType::Object(child) => key_at(child, offset).map(|mut path| {
+                    path.insert(0, key.value.to_string());
+                    path
+                }),
+                JsonType::String(_) => Some(vec![key.value.to_string()]),
+            };
+        }
+    }
+
+    None
+}
+
+fn format_key(key: &[String]) -> String {
+    key.iter()
+        .map(|p| format!("\"{}\"", p))
+        .collect::<Vec<_>>()
+        .join("->")
+}
+
+fn diagnostic(range: Range, severity: DiagnosticSeverity, message: String) -> LspDiagnostic {
+    LspDiagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("jsoncpl".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+fn respond<P: DeserializeOwned, R: Serialize>(request: Request, handler: impl FnOnce(P) -> R) -> Response {
+    match serde_json::from_value::<P>(request.params) {
+        Ok(params) => Response::new_ok(request.id, handler(params)),
+        Err(e) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, e.to_string()),
+    }
+}
+
+fn path(uri: &Url) -> Option<PathBuf> {
+    let path = uri.to_file_path().ok()?;
+
+    Some(path.canonicalize().unwrap_or(path))
+}
+
+pub struct Server {
+    style: JsonStyle,
+    suppressions: Suppressions,
+    severities: Severities,
+    roots: Vec<PathBuf>,
+    documents: HashMap<PathBuf, String>,
+}
+
+impl Server {
+    pub fn new(style: JsonStyle, folders: &[PathBuf], suppressions: Suppressions, severities: Severities) -> Server {
+        Server {
+            style,
+            suppressions,
+            severities,
+            roots: folders.iter()
+                .map(|folder| folder.canonicalize().unwrap_or(folder.clone()))
+                .collect(),
+            documents: HashMap::new(),
+        }
+    }
+
+    fn serve(&mut self, connection: &Connection) -> LspResult<()> {
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    connection.sender.send(Message::Response(self.handle_request(request)))?;
+                }
+                Message::Notification(notification) => {
+                    for (uri, diagnostics) in self.handle_notification(notification) {
+                        connection.sender.send(Message::Notification(Notification::new(
+                            PublishDiagnostics::METHOD.to_string(),
+                            PublishDiagnosticsParams::new(uri, diagnostics, None),
+                        )))?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        match request.method.as_str() {
+            Formatting::METHOD => respond(request, |params: DocumentFormattingParams| {
+                self.formatting(&params.text_document.uri)
+            }),
+            CodeActionRequest::METHOD => respond(request, |params: CodeActionParams| {
+                self.code_actions(&params.text_document.uri)
+            }),
+            GotoDefinition::METHOD => respond(request, |params: GotoDefinitionParams| {
+                let params = params.text_document_position_params;
+                self.definition(&params.text_document.uri, params.position)
+            }),
+            method => {
+                let message = format!("Unknown method {}", method);
+                Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message)
+            }
+        }
+    }
+
+    /// Updates the open documents and returns the diagnostics to publish.
+    fn handle_notification(&mut self, notification: Notification) -> Vec<(Url, Vec<LspDiagnostic>)> {
+        let (uri, closed) = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                match serde_json::from_value::<DidOpenTextDocumentParams>(notification.params) {
+                    Ok(params) => {
+                        let document = params.text_document;
+                        if let Some(path) = path(&document.uri) {
+                            self.documents.insert(path, document.text);
+                        }
+                        (document.uri, false)
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                match serde_json::from_value::<DidChangeTextDocumentParams>(notification.params) {
+                    Ok(mut params) => {
+                        let uri = params.text_document.uri;
+                        if let (Some(path), Some(change)) = (path(&uri), params.content_changes.pop()) {
+                            self.documents.insert(path, change.text);
+                        }
+                        (uri, false)
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                match serde_json::from_value::<DidCloseTextDocumentParams>(notification.params) {
+                    Ok(params) => {
+                        let uri = params.text_document.uri;
+                        if let Some(path) = path(&uri) {
+                            self.documents.remove(&path);
+                        }
+                        (uri, true)
+                    }
+                    Err(_) => return Vec::new(),
+                }
+            }
+            _ => return Vec::new(),
+        };
+
+        let Some(path) = path(&uri) else {
+            return Vec::new();
+        };
+        // A change affects the missing keys of the open siblings as well
+        let mut published = vec![(uri, if closed { Vec::new() } else { self.diagnostics(&path) })];
+        for sibling in self.siblings(&path) {
+            if self.documents.contains_key(&sibling) {
+                if let Ok(uri) = Url::from_file_path(&sibling) {
+                    published.push((uri, self.diagnostics(&sibling)));
+                }
+            }
+        }
+
+        published
+    }
+
+    /// The files with the same path relative to their folder in the other folders.
+    fn siblings(&self, path: &Path) -> Vec<PathBuf> {
+        let Some(relative) = self.roots.iter().find_map(|root| path.strip_prefix(root).ok()) else {
+            return Vec::new();
+        };
+
+        self.roots.iter()
+            .map(|root| root.join(relative))
+            .filter(|sibling| sibling != path)
+            .filter(|sibling| self.documents.contains_key(sibling) || sibling.is_file())
+            .collect()
+    }
+
+    /// Loads a file, preferring the content of the open document.
+    fn load(&self, path: &Path) -> Option<LoadedFile> {
+        let content = match self.documents.get(path) {
+            Some(content) => content.clone(),
+            None => std::fs::read_to_string(path).ok()?,
+        };
+
+        Some(LoadedFile::new(path.to_path_buf(), content))
+    }
+
+    fn load_siblings(&self, path: &Path) -> Vec<LoadedFile> {
+        self.siblings(path).iter()
+            .filter_map(|sibling| self.load(sibling))
+            .collect()
+    }
+
+    /// The severity of the diagnostics of the rule with the ID, `None` if the rule is off.
+    fn severity(&self, rule: &str) -> Option<DiagnosticSeverity> {
+        match self.severities.of_rule(rule) {
+            Severity::Off => None,
+            Severity::Warn => Some(DiagnosticSeverity::WARNING),
+            Severity::Error => Some(DiagnosticSeverity::ERROR),
+        }
+    }
+
+    pub fn diagnostics(&self, path: &Path) -> Vec<LspDiagnostic> {
+        let Some(file) = self.load(path) else {
+            return Vec::new();
+        };
+        let siblings = self.load_siblings(path);
+        let text = file.content();
+        let mut diagnostics = Vec::new();
+
+        let json = match Parser::new(self.style).parse(text) {
+            Ok((json, style_errors)) => {
+                let mut suppressions = self.suppressions.clone();
+                suppressions.collect(&json);
+                for style_error in style_errors.iter().filter(|error| !suppressions.suppresses_style(&json, error)) {
+                    if let Some(severity) = self.severity(style_error.kind.rule()) {
+                        diagnostics.push(diagnostic(
+                            char_range(text, style_error.span.location_offset()),
+                            severity,
+                            format!("I {}", style_error.kind.description(&self.style)),
+                        ));
+                    }
+                }
+
+                json
+            }
+            Err(e) => {
+                let offset = e.errors.first().map(|(s, _)| s.location_offset()).unwrap_or(0);
+                if let Some(severity) = self.severity("parse") {
+                    diagnostics.push(diagnostic(
+                        char_range(text, offset),
+                        severity,
+                        "Can not parse json".to_string(),
+                    ));
+                }
+
+                return diagnostics;
+            }
+        };
+
+        let mut jsons = vec![(&file, json)];
+        for sibling in &siblings {
+            if let Ok((json, _)) = Parser::new(JsonStyle::IGNORE).parse(sibling.content()) {
+                jsons.push((sibling, json));
+            }
+        }
+        let mut errors = Vec::new();
+        check_directives(&file, &jsons[0].1, &mut errors);
+        entry_parity(&jsons, &self.suppressions, &mut errors);
+        errors.retain(|error| !self.suppressions.suppresses(error));
+
+        let (_, json) = &jsons[0];
+        for error in errors {
+            let Some(severity) = self.severity(error.rule()) else {
+                continue;
+            };
+            match error {
+                Diagnostic::MissingKey { file, key } if file == path => {
+                    let found = lookup(json, &key);
+                    let range = match found.last() {
+                        Some(parent) => key_range(text, parent),
+                        None => char_range(text, 0),
+                    };
+                    diagnostics.push(diagnostic(
+                        range,
+                        severity,
+                        format!("Can not find key `{}`, it exists in other files", format_key(&key)),
+                    ));
+                }
+                Diagnostic::MissingKey { file, key } => {
+                    let found = lookup(json, &key);
+                    if found.len() == key.len() {
+                        diagnostics.push(diagnostic(
+                            key_range(text, found.last().unwrap()),
+                            severity,
+                            format!("Can not find key `{}` in file {}", format_key(&key), file.display()),
+                        ));
+                    }
+                }
+                Diagnostic::TypeMismatch { key, left, right } => {
+                    for location in [left, right].iter().filter(|location| location.file == path) {
+                        let offset = line_column_offset(text, location.start_line, location.column);
+                        diagnostics.push(diagnostic(
+                            char_range(text, offset),
+                            severity,
+                            format!("Found different value types for key `{}`", format_key(&key)),
+                        ));
+                    }
+                }
+                Diagnostic::InvalidDirective { location, message, .. } => {
+                    let offset = line_column_offset(text, location.start_line, location.column);
+                    diagnostics.push(diagnostic(
+                        char_range(text, offset),
+                        severity,
+                        format!("Invalid directive: {}", message),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    fn formatting(&self, uri: &Url) -> Option<Vec<TextEdit>> {
+        let file = self.load(&path(uri)?)?;
+        let (json, _) = Parser::new(JsonStyle::IGNORE).parse(file.content()).ok()?;
+        let generated = Generator::new(self.style).generate(json);
+        if generated == *file.content() {
+            Some(Vec::new())
+        } else {
+            Some(vec![TextEdit::new(full_range(file.content()), generated)])
+        }
+    }
+
+    pub fn code_actions(&self, uri: &Url) -> Vec<CodeActionOrCommand> {
+        let Some(path) = path(uri) else {
+            return Vec::new();
+        };
+        let Some(file) = self.load(&path) else {
+            return Vec::new();
+        };
+        let Ok((json, _)) = Parser::new(JsonStyle::IGNORE).parse(file.content()) else {
+            return Vec::new();
+        };
+        let siblings = self.load_siblings(&path);
+        let generator = Generator::new(self.style);
+        let current = generator.generate(json.clone());
+        let action = |title: &str, kind: CodeActionKind, edit: TextEdit| {
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.to_string(),
+                kind: Some(kind),
+                edit: Some(WorkspaceEdit::new(HashMap::from([(uri.clone(), vec![edit])]))),
+                ..Default::default()
+            })
+        };
+
+        let mut actions = Vec::new();
+        let sorted = matches!(
+            self.style,
+            JsonStyle::STYLED { sort_algorithm, key_order, .. } if !matches!(sort_algorithm, SortAlgorithm::NONE) || key_order.is_some()
+        );
+        if sorted && current != *file.content() {
+            actions.push(action("Sort keys", CodeActionKind::SOURCE, TextEdit::new(full_range(file.content()), current.clone())));
+        }
+
+        let mut completed = JsonDocument::from(json);
+        for sibling in &siblings {
+            if let Ok((sibling, _)) = Parser::new(JsonStyle::IGNORE).parse(sibling.content()) {
+                insert_missing(&mut completed, &JsonDocument::from(sibling));
+            }
+        }
+        let completed = generator.generate(completed);
+        if completed != current {
+            // Only touches the inserted keys if the file is formatted
+            actions.push(action("Insert missing keys", CodeActionKind::QUICKFIX, minimal_edit(file.content(), &completed)));
+        }
+
+        actions
+    }
+
+    pub fn definition(&self, uri: &Url, position: Position) -> Vec<Location> {
+        let Some(path) = path(uri) else {
+            return Vec::new();
+        };
+        let Some(file) = self.load(&path) else {
+            return Vec::new();
+        };
+        let Ok((json, _)) = Parser::new(JsonStyle::IGNORE).parse(file.content()) else {
+            return Vec::new();
+        };
+        let Some(key) = key_at(&json, offset(file.content(), position)) else {
+            return Vec::new();
+        };
+
+        self.load_siblings(&path).iter()
+            .filter_map(|sibling| {
+                let (json, _) = Parser::new(JsonStyle::IGNORE).parse(sibling.content()).ok()?;
+                let found = lookup(&json, &key);
+                if found.len() != key.len() {
+                    return None;
+                }
+
+                Some(Location::new(
+                    Url::from_file_path(sibling.path()).ok()?,
+                    key_range(sibling.content(), found.last()?),
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use jsoncpl::checks::suppress::IgnoreRule;
+    use jsoncpl::parser::model::{LineEnding, SortOrder};
+    use jsoncpl::severity::RuleSeverity;
+
+    const STYLE: JsonStyle = JsonStyle::STYLED {
+        line_endings: LineEnding::LF,
+        indentation: Some("    "),
+        post_colon: Some(" "),
+        order: SortOrder::ASC,
+        sort_algorithm: SortAlgorithm::NORMAL,
+        key_order: None,
+    };
+
+    fn server() -> Server {
+        Server::new(STYLE, &[
+            PathBuf::from("testdata/i18n/fr"),
+            PathBuf::from("testdata/i18n/de"),
+            PathBuf::from("testdata/i18n/en"),
+        ], Suppressions::default(), Severities::default())
+    }
+
+    fn uri(path: &str) -> Url {
+        Url::from_file_path(PathBuf::from(path).canonicalize().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn positions() {
+        let text = "{\n    \"ä\": \"😀x\"\n}";
+        let x = text.find('x').unwrap();
+        assert_eq!(position(text, x), Position::new(1, 12));
+        assert_eq!(offset(text, Position::new(1, 12)), x);
+        assert_eq!(offset(text, Position::new(1, 100)), text.rfind('\n').unwrap());
+        assert_eq!(line_column_offset(text, 2, 6), text.find('ä').unwrap());
+    }
+
+    #[test]
+    fn minimal_edits() {
+        let edit = minimal_edit("{\"ä\": \"b\"}", "{\"ä\": \"c\", \"d\": \"\"}");
+        assert_eq!(edit.range, Range::new(Position::new(0, 7), Position::new(0, 8)));
+        assert_eq!(edit.new_text, "c\", \"d\": \"");
+        assert_eq!(minimal_edit("{}", "{}").new_text, "");
+    }
+
+    #[test]
+    fn missing_key_diagnostics() {
+        let mut server = server();
+        let path = path(&uri("testdata/i18n/fr/common.json")).unwrap();
+        server.documents.insert(path.clone(), std::fs::read_to_string(&path).unwrap());
+
+        let diagnostics = server.diagnostics(&path);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start, Position::new(2, 4));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert!(diagnostics[0].message.contains("\"shared\"->\"HIDE_INTRO\""));
+
+        // The rules are configured like for lint
+        server.severities = Severities::new(vec![RuleSeverity::parse("missing-key=warn").unwrap()]);
+        assert_eq!(server.diagnostics(&path)[0].severity, Some(DiagnosticSeverity::WARNING));
+        server.suppressions = Suppressions::new(vec![IgnoreRule::parse("shared=missing-key").unwrap()]);
+        assert!(server.diagnostics(&path).is_empty());
+    }
+
+    #[test]
+    fn insert_missing_keys() {
+        let actions = server().code_actions(&uri("testdata/i18n/fr/common.json"));
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            CodeActionOrCommand::CodeAction(action) => {
+                assert_eq!(action.title, "Insert missing keys");
+                let edits = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+                let edit = &edits.values().next().unwrap()[0];
+                // The key is inserted with an empty value instead of the German text
+                assert_eq!(edit.range, Range::new(Position::new(3, 9), Position::new(3, 9)));
+                assert_eq!(edit.new_text, "HIDE_INTRO\": \"\",\n        \"");
+            }
+            CodeActionOrCommand::Command(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn definition_in_siblings() {
+        let locations = server().definition(
+            &uri("testdata/i18n/fr/common.json"),
+            Position::new(3, 10),
+        );
+        assert_eq!(
+            locations.iter().map(|location| location.uri.clone()).collect::<Vec<_>>(),
+            vec![uri("testdata/i18n/de/common.json"), uri("testdata/i18n/en/common.json")],
+        );
+        assert_eq!(locations[0].range.start, Position::new(4, 8));
+    }
+}
@@ -1,20 +1,36 @@
-use std::fs;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use clap::Parser;
 use colored::Colorize;
-use crate::checks::entry_parity::entry_parity;
-use crate::checks::file_parity::file_parity;
-use crate::checks::file_style::file_style;
-use crate::cli::{Cli, Commands, Indentation};
-use crate::io::{File, read_folders};
-use crate::parser::model::{JsonStyle, LineEnding, SortAlgorithm, SortOrder};
+use regex::Regex;
+use jsoncpl::baseline::Baseline;
+use jsoncpl::checks::suppress::Suppressions;
+use jsoncpl::checks::values::ValueChecks;
+use jsoncpl::config::{Config, CONFIG_FILE};
+use jsoncpl::diagnostic::Diagnostic;
+use jsoncpl::edit::{edit, get, Edit};
+use jsoncpl::io::{File, LoadedFile, read_file_in_folders, read_folders};
+use jsoncpl::lint::{fix, format, lint, undefined_keys, unused, LintOptions, Report};
+use jsoncpl::parser::naming::KeyNaming;
+use jsoncpl::parser::order::KeyOrder;
+use jsoncpl::severity::{Severities, Severity};
+use jsoncpl::stats::{csv, stats, table};
+use jsoncpl::usage::{patterns, scan_sources, Usages};
+use jsoncpl::{JsonStyle, KeyPath, LineEnding, SortAlgorithm, SortOrder};
+use crate::cli::{Cli, Commands, Indentation, RuleArgs, StatsFormat, WatchMode};
+use crate::html::coverage;
 
 mod cli;
-mod io;
-mod parser;
-mod checks;
-mod util;
-mod natural_sort;
+#[cfg(test)]
+mod fixture;
+mod git;
+mod html;
+mod lsp;
+mod watch;
+
+/// The key order of the style, it is used until the program exits.
+static KEY_ORDER: OnceLock<KeyOrder> = OnceLock::new();
 
 fn cli_to_style(cli: &Cli) -> JsonStyle {
     JsonStyle::STYLED {
@@ -39,61 +55,382 @@ fn cli_to_style(cli: &Cli) -> JsonStyle {
         sort_algorithm: match cli.algorithm {
             cli::SortAlgorithm::Natural => SortAlgorithm::NATURAL,
             cli::SortAlgorithm::Default => SortAlgorithm::NORMAL,
+            cli::SortAlgorithm::CaseInsensitive => SortAlgorithm::CASE_INSENSITIVE,
+            cli::SortAlgorithm::NaturalCaseInsensitive => SortAlgorithm::NATURAL_CASE_INSENSITIVE,
+            cli::SortAlgorithm::Collation => SortAlgorithm::COLLATION,
             cli::SortAlgorithm::IGNORE => SortAlgorithm::NONE,
-        }
+        },
+        key_order: (!cli.pinned.is_empty() || cli.group.is_some()).then(|| KEY_ORDER.get_or_init(|| KeyOrder {
+            pinned: cli.pinned.clone(),
+            grouping: cli.group,
+        })),
     }
 }
 
-fn lint(cli: Cli, folders: Vec<(PathBuf, Vec<File>)>) -> bool {
-    let mut errors = Vec::new();
-    let file_types = file_parity(folders, &mut errors);
+/// Prints the report and whether the run succeeded, it fails on errors and on more than `max_warnings` warnings.
+fn print_report(style: &JsonStyle, report: &Report, max_warnings: Option<usize>) -> bool {
+    if !report.is_empty() {
+        println!("{}", report.render(style).join("\n"));
+        for (folder, count) in &report.untranslated {
+            println!("[{}] {} untranslated values in {}", "UNTRANSLATED".yellow(), count, folder.to_str().unwrap().green());
+        }
+    }
+    let warnings = report.count(Severity::Warn);
+    let too_many = max_warnings.is_some_and(|max| warnings > max);
+    let succeeded = report.count(Severity::Error) == 0 && !too_many;
+    if too_many {
+        println!("\n[{}] {} warnings exceed the maximum of {}", "ERROR".red(), warnings, max_warnings.unwrap());
+    }
+    if !succeeded {
+        println!("\n{}", "Linting failed".red());
+    } else if warnings > 0 {
+        println!("\n{}", format!("Linting succeeded with {} warnings", warnings).yellow());
+    }
+
+    succeeded
+}
 
-    let style = cli_to_style(&cli);
-    for file_type in file_types {
-        let loaded_files = file_type.into_iter()
-            .map(|file| file.load())
-            .collect::<Vec<_>>();
-        let mut jsons = loaded_files.iter().map(|file| {
-            let json = file_style(&style, file, &mut errors).unwrap();
+fn print_format_errors(style: &JsonStyle, folders: Vec<(PathBuf, Vec<File>)>) {
+    for error in format(style, folders) {
+        println!("{}", error.render(style, &[]));
+    }
+}
 
-            (file, json)
-        }).collect::<Vec<_>>();
-        entry_parity(&mut jsons, &mut errors);
+fn scan(sources: &[String], regexes: &[String]) -> Usages {
+    let regexes = match patterns(regexes) {
+        Ok(regexes) => regexes,
+        Err(e) => {
+            println!("[{}] Invalid pattern: {}", "ERROR".red(), e);
+            std::process::exit(1);
+        }
+    };
+    match scan_sources(sources, &regexes) {
+        Ok(usages) => usages,
+        Err(e) => {
+            println!("[{}] Can not scan the sources: {}", "ERROR".red(), e);
+            std::process::exit(1);
+        }
     }
+}
 
-    if errors.len() > 0 {
-        println!("{}", errors.join("\n"));
+/// Loads the file at `relative_path` of every folder, missing files are reported.
+fn load_file_in_folders(style: &JsonStyle, folders: &[PathBuf], relative_path: &Path) -> Vec<LoadedFile> {
+    let mut files = Vec::new();
+    for (folder, found) in read_file_in_folders(folders, relative_path) {
+        if found.is_empty() {
+            let not_found = Diagnostic::NotFound {
+                file: relative_path.to_path_buf(),
+                folder,
+            };
+            println!("{}", not_found.render(style, &[]));
+        }
+        files.extend(found.into_iter().map(File::load));
     }
 
-    errors.is_empty()
+    files
 }
 
-fn format(cli: Cli, folders: Vec<(PathBuf, Vec<File>)>) {
-    let style = cli_to_style(&cli);
-    for (_, files) in folders {
-        for file in files {
-            let file = file.load();
-            let (parsed, _) = parser::parser::Parser::new(
-                JsonStyle::IGNORE
-            ).parse(file.content()).unwrap();
-            let generated = parser::generator::Generator::new(style).generate(parsed);
-            fs::write(file.path(), &generated).unwrap();
+fn run_edit(style: &JsonStyle, folders: &[PathBuf], relative_path: &Path, change: Edit) {
+    let files = load_file_in_folders(style, folders, relative_path);
+    match edit(style, &files, &change) {
+        Ok(written) => {
+            for file in written {
+                println!("[{}] {}", "UPDATED".green(), file.to_str().unwrap().green());
+            }
+        }
+        Err(errors) => {
+            for (file, error) in errors {
+                println!("[{}] {}\n {}", "ERROR".red(), error, file.to_str().unwrap().green());
+            }
+            std::process::exit(1);
         }
     }
 }
 
+/// The ignored keys and the severities of the config file followed by the ones of the command line.
+fn load_rules(args: &RuleArgs) -> (Suppressions, Severities) {
+    let config = match &args.config {
+        Some(path) => Config::load(path).map_err(|e| (path.clone(), e)),
+        None if Path::new(CONFIG_FILE).exists() => {
+            Config::load(Path::new(CONFIG_FILE)).map_err(|e| (PathBuf::from(CONFIG_FILE), e))
+        }
+        None => Ok(Config::default()),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err((path, e)) => {
+            println!("[{}] Can not read the config: {}\n{}", "ERROR".red(), e, path.to_str().unwrap().green());
+            std::process::exit(1);
+        }
+    };
+
+    (
+        Suppressions::new([config.ignore, args.ignore.clone()].concat()),
+        Severities::new([config.severities, args.rules.clone()].concat()),
+    )
+}
+
 fn main() {
     let cli: Cli = Cli::parse();
 
+    let style = cli_to_style(&cli);
     match &cli.command {
         Commands::Format { folders } => {
             let folders = read_folders(folders);
-            format(cli, folders);
+            print_format_errors(&style, folders);
         }
-        Commands::Lint { folders } => {
+        Commands::Lint {
+            folders,
+            jobs,
+            cache_dir,
+            changed_since,
+            untranslated,
+            allowed,
+            empty_values,
+            whitespace,
+            double_spaces,
+            markers,
+            object_keys,
+            leaf_keys,
+            fix: fix_keys,
+            max_length,
+            max_ratio,
+            rules,
+            max_warnings,
+            baseline,
+            write_baseline,
+        } => {
+            let (suppressions, severities) = load_rules(rules);
+            let changed = changed_since.as_ref().map(|rev| {
+                let mut changed = HashSet::new();
+                for folder in folders {
+                    match git::changed_files(folder, rev) {
+                        Ok(files) => changed.extend(files),
+                        Err(e) => {
+                            println!("[{}] Can not list the changed files: {}", "ERROR".red(), e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+
+                changed
+            });
+            let naming = KeyNaming {
+                objects: object_keys.clone(),
+                leaves: leaf_keys.clone(),
+            };
+            if *fix_keys {
+                for error in fix(&style, &naming, read_folders(folders)) {
+                    println!("{}", error.render(&style, &[]));
+                }
+            }
             let folders = read_folders(folders);
-            if !lint(cli, folders) {
-                println!("\n{}", "Linting failed".red());
+            let options = LintOptions {
+                jobs: *jobs,
+                cache_dir: cache_dir.clone(),
+                changed,
+                untranslated: untranslated.then(|| allowed.clone()),
+                values: ValueChecks {
+                    empty: *empty_values,
+                    whitespace: *whitespace,
+                    double_spaces: *double_spaces,
+                    markers: match markers.iter().map(|marker| Regex::new(marker)).collect() {
+                        Ok(markers) => markers,
+                        Err(e) => {
+                            println!("[{}] Invalid marker: {}", "ERROR".red(), e);
+                            std::process::exit(1);
+                        }
+                    },
+                },
+                naming,
+                lengths: [max_length.as_slice(), max_ratio.as_slice()].concat(),
+                suppressions,
+                severities,
+            };
+            let mut report = lint(&style, folders, &options);
+            if let Some(e) = &report.cache_error {
+                println!("[{}] Can not write cache: {}", "WARNING".yellow(), e);
+            }
+            if let Some(path) = baseline {
+                if *write_baseline {
+                    let baseline = Baseline::new(&report);
+                    if let Err(e) = baseline.store(path) {
+                        println!("[{}] Can not write the baseline: {}", "ERROR".red(), e);
+                        std::process::exit(1);
+                    }
+                    println!("[{}] Recorded {} diagnostics in {}", "BASELINE".yellow(), baseline.len(), path.to_str().unwrap().green());
+                    return;
+                }
+                let removable = match Baseline::load(path) {
+                    Ok(baseline) => baseline.apply(&mut report),
+                    Err(e) => {
+                        println!("[{}] Can not read the baseline: {}\n{}", "ERROR".red(), e, path.to_str().unwrap().green());
+                        std::process::exit(1);
+                    }
+                };
+                // Only a part of the groups was linted, the other entries are not removable
+                if options.changed.is_none() {
+                    for (fingerprint, count) in removable {
+                        let key = match fingerprint.key.is_empty() {
+                            true => String::new(),
+                            false => format!(" of key `{}`", fingerprint.key.join(".")),
+                        };
+                        let rule = match &fingerprint.kind {
+                            Some(kind) => format!("{} ({})", fingerprint.rule, kind),
+                            None => fingerprint.rule.clone(),
+                        };
+                        println!(
+                            "[{}] {}x `{}`{} in {} no longer occurs",
+                            "REMOVABLE".yellow(),
+                            count,
+                            rule,
+                            key,
+                            fingerprint.file.to_str().unwrap().green(),
+                        );
+                    }
+                }
+            }
+            if !print_report(&style, &report, *max_warnings) {
+                std::process::exit(1);
+            }
+        }
+        Commands::Lsp { folders, rules } => {
+            let (suppressions, severities) = load_rules(rules);
+            if let Err(e) = lsp::run(style, folders, suppressions, severities) {
+                eprintln!("[{}] The language server failed: {}", "ERROR".red(), e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Watch { mode, folders, rules } => {
+            let (suppressions, severities) = load_rules(rules);
+            let options = LintOptions {
+                suppressions,
+                severities,
+                ..LintOptions::default()
+            };
+            let result = watch::watch(folders, |relative_paths| {
+                // Clear the terminal for a fresh report of every changed group
+                print!("\x1B[2J\x1B[1;1H");
+                for relative_path in relative_paths {
+                    println!("{}", relative_path.to_str().unwrap().green());
+                    let group = read_file_in_folders(folders, relative_path);
+                    match mode {
+                        WatchMode::Lint => {
+                            print_report(&style, &lint(&style, group, &options), None);
+                        }
+                        WatchMode::Format => print_format_errors(&style, group),
+                    }
+                }
+            });
+            if let Err(e) = result {
+                println!("[{}] Can not watch the folders: {}", "ERROR".red(), e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Unused { sources, patterns, dynamic, folders } => {
+            let usages = scan(sources, patterns);
+            let report = unused(read_folders(folders), &usages, *dynamic);
+            if !report.is_empty() {
+                println!("{}", report.render(&style).join("\n"));
+                std::process::exit(1);
+            }
+        }
+        Commands::Extract { file, sources, patterns, insert, placeholder, folders } => {
+            let usages = scan(sources, patterns);
+            // The reference file is missing if the first folder lacks it
+            let files = load_file_in_folders(&style, folders, file);
+            let reference_path = folders.first().map(|first| first.join(file));
+            let Some(reference) = files.iter().find(|loaded| Some(loaded.path()) == reference_path.as_ref()) else {
+                println!(
+                    "[{}] Can not extract keys without the reference file {}",
+                    "ERROR".red(),
+                    reference_path.unwrap_or_default().to_str().unwrap().green(),
+                );
+                std::process::exit(1);
+            };
+            // Keys defined in another file of the reference folder are not undefined
+            let siblings = read_folders(&folders[..1]).into_iter()
+                .flat_map(|(_, files)| files)
+                .filter(|sibling| sibling.path() != reference.path())
+                .map(File::load)
+                .collect::<Vec<_>>();
+            let errors = undefined_keys(reference, &siblings, &usages);
+            for error in &errors {
+                println!("{}", error.render(&style, &[]));
+            }
+            let paths = errors.iter()
+                .filter_map(|error| match error {
+                    Diagnostic::UndefinedKey { key, .. } => Some(KeyPath::new(key.clone())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if *insert && paths.len() == errors.len() {
+                if !paths.is_empty() {
+                    run_edit(&style, folders, file, Edit::Insert(paths, placeholder.clone()));
+                }
+            } else if !errors.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Get { file, path, folders } => {
+            let files = load_file_in_folders(&style, folders, file);
+            let mut failed = false;
+            for (file, value) in get(&style, &files, path) {
+                match value {
+                    Ok(Some(value)) => println!("{}\n{}", file.to_str().unwrap().green(), value),
+                    Ok(None) => {
+                        failed = true;
+                        println!("{}\n[{}] Can not find key `{}`", file.to_str().unwrap().green(), "MISSING".yellow(), path);
+                    }
+                    Err(e) => {
+                        failed = true;
+                        println!("{}\n[{}] {}", file.to_str().unwrap().green(), "ERROR".red(), e);
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Commands::Set { file, path, value, folders } => {
+            run_edit(&style, folders, file, Edit::Set(path.clone(), value.clone()));
+        }
+        Commands::Rm { file, path, folders } => {
+            run_edit(&style, folders, file, Edit::Remove(path.clone()));
+        }
+        Commands::Mv { file, from, to, force, folders } => {
+            run_edit(&style, folders, file, Edit::Move {
+                from: from.clone(),
+                to: to.clone(),
+                force: *force,
+            });
+        }
+        Commands::Stats { format, output, folders } => {
+            let (locales, errors) = stats(read_folders(folders));
+            for error in &errors {
+                println!("{}", error.render(&style, &[]));
+            }
+            let rendered = match format {
+                StatsFormat::Table => table(&locales),
+                StatsFormat::Json => serde_json::to_string_pretty(&locales).unwrap(),
+                StatsFormat::Csv => csv(&locales),
+                StatsFormat::Html => {
+                    // The links to the files are relative to the page
+                    let base = output.as_ref()
+                        .and_then(|output| output.parent())
+                        .unwrap_or(Path::new(""));
+                    let report = lint(&style, read_folders(folders), &LintOptions::default());
+                    coverage(&report, &locales, base)
+                }
+            };
+            match output {
+                Some(output) => if let Err(e) = std::fs::write(output, rendered) {
+                    println!("[{}] {}\n{}", "ERROR".red(), e, output.to_str().unwrap().green());
+                    std::process::exit(1);
+                },
+                None => println!("{}", rendered),
+            }
+            if !errors.is_empty() {
                 std::process::exit(1);
             }
         }
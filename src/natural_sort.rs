@@ -65,6 +65,7 @@ fn char_to_digit(c: &char) -> Result<u32, ()> {
     }
 }
 
+#[allow(clippy::match_like_matches_macro)]
 fn is_digit(c: &char) -> bool {
     match c {
         '0' => true,
@@ -0,0 +1,185 @@
+use std::cmp::Ordering;
+use std::fmt;
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::parser::model::{JsonObject, JsonType};
+
+/// An owned json object which, unlike [`JsonObject`], does not borrow from a source text.
+///
+/// Keys and strings are stored as written between the quotes, so escape sequences are kept and a
+/// document converted from a parsed [`JsonObject`] generates the same text. The serde integration
+/// decodes them on serialization and escapes them on deserialization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonDocument {
+    pub values: Vec<(String, JsonValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonValue {
+    Object(JsonDocument),
+    String(String),
+}
+
+impl JsonDocument {
+    pub fn new() -> JsonDocument {
+        JsonDocument::default()
+    }
+
+    /// Appends an entry, the key and a string value are escaped.
+    pub fn with(mut self, key: &str, value: impl Into<JsonValue>) -> JsonDocument {
+        self.values.push((escape(key), value.into()));
+        self
+    }
+
+    pub fn sort_by<F>(&mut self, mut compare: F)
+        where
+            F: FnMut(&str, &str) -> Ordering + Copy, {
+        self.values.sort_by(|(a, _), (b, _)| compare(a, b));
+        self.values.iter_mut().for_each(|(_, value)| {
+            if let JsonValue::Object(object) = value {
+                object.sort_by(compare);
+            }
+        })
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(escape(value))
+    }
+}
+
+impl From<JsonDocument> for JsonValue {
+    fn from(value: JsonDocument) -> Self {
+        JsonValue::Object(value)
+    }
+}
+
+impl<'a> From<&JsonObject<'a>> for JsonDocument {
+    fn from(object: &JsonObject<'a>) -> Self {
+        JsonDocument {
+            values: object.values.iter()
+                .map(|(key, value)| (key.value.to_string(), JsonValue::from(value)))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> From<JsonObject<'a>> for JsonDocument {
+    fn from(object: JsonObject<'a>) -> Self {
+        JsonDocument::from(&object)
+    }
+}
+
+impl<'a> From<&JsonType<'a>> for JsonValue {
+    fn from(value: &JsonType<'a>) -> Self {
+        match value {
+            JsonType::Object(object) => JsonValue::Object(JsonDocument::from(object)),
+            JsonType::String(string) => JsonValue::String(string.value.to_string()),
+        }
+    }
+}
+
+/// Escapes `value` to be written between the quotes of a json string.
+pub(crate) fn escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Decodes the escape sequences of a json string, invalid sequences are kept as they are.
+pub(crate) fn unescape(value: &str) -> String {
+    serde_json::from_str(&format!("\"{}\"", value)).unwrap_or_else(|_| value.to_string())
+}
+
+impl Serialize for JsonDocument {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for (key, value) in &self.values {
+            map.serialize_entry(&unescape(key), value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for JsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonValue::Object(object) => object.serialize(serializer),
+            JsonValue::String(string) => serializer.serialize_str(&unescape(string)),
+        }
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or an object of strings and objects")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(JsonValue::from(value))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut document = JsonDocument::new();
+        while let Some((key, value)) = map.next_entry::<String, JsonValue>()? {
+            document.values.push((escape(&key), value));
+        }
+
+        Ok(JsonValue::Object(document))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonDocument {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match JsonValue::deserialize(deserializer)? {
+            JsonValue::Object(object) => Ok(object),
+            JsonValue::String(_) => Err(de::Error::custom("expected an object")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::model::JsonStyle;
+    use crate::parser::parser::Parser;
+    use super::*;
+
+    #[test]
+    fn from_parsed_object() {
+        let (json, _) = Parser::new(JsonStyle::IGNORE)
+            .parse("{\"a\": \"line\\nbreak\", \"b\": {\"c\": \"d\"}}")
+            .unwrap();
+
+        assert_eq!(JsonDocument::from(json), JsonDocument::new()
+            .with("a", "line\nbreak")
+            .with("b", JsonDocument::new().with("c", "d")));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let source = "{\"b\":{\"c\":\"\\\"quoted\\\"\"},\"a\":\"ä\"}";
+        let document: JsonDocument = serde_json::from_str(source).unwrap();
+
+        assert_eq!(document.values[0], ("b".to_string(), JsonValue::Object(JsonDocument {
+            values: vec![("c".to_string(), JsonValue::String("\\\"quoted\\\"".to_string()))],
+        })));
+        assert_eq!(serde_json::to_string(&document).unwrap(), source);
+    }
+
+    #[test]
+    fn deserialize_rejects_other_values() {
+        assert!(serde_json::from_str::<JsonDocument>("{\"a\": 1}").is_err());
+        assert!(serde_json::from_str::<JsonDocument>("\"a\"").is_err());
+    }
+}
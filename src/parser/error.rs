@@ -0,0 +1,114 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use crate::parser::model::{JsonStyle, Span};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum StyleErrorKind {
+    MaybeSpace,
+    PostColon,
+    PostColonTooMuch,
+    Crlf,
+    Lf,
+    NoBreak,
+    AnyBreak,
+    CrButNotLf,
+    NotEnoughIndentation,
+    TooMuchIndentation,
+    Sorting,
+    ObjectKeyNaming,
+    LeafKeyNaming,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct StyleError<'a> {
+    pub kind: StyleErrorKind,
+    pub span: Span<'a>,
+}
+
+impl StyleErrorKind {
+    /// The ID of the rule reporting this kind of violation.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            StyleErrorKind::MaybeSpace => "space",
+            StyleErrorKind::PostColon | StyleErrorKind::PostColonTooMuch => "post-colon",
+            StyleErrorKind::Crlf
+            | StyleErrorKind::Lf
+            | StyleErrorKind::NoBreak
+            | StyleErrorKind::AnyBreak
+            | StyleErrorKind::CrButNotLf => "line-ending",
+            StyleErrorKind::NotEnoughIndentation | StyleErrorKind::TooMuchIndentation => "indentation",
+            StyleErrorKind::Sorting => "sorting",
+            StyleErrorKind::ObjectKeyNaming => "object-key-naming",
+            StyleErrorKind::LeafKeyNaming => "leaf-key-naming",
+        }
+    }
+
+    /// The stable name of the kind, it distinguishes the kinds of violations of a rule.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StyleErrorKind::MaybeSpace => "space",
+            StyleErrorKind::PostColon => "post-colon",
+            StyleErrorKind::PostColonTooMuch => "post-colon-too-much",
+            StyleErrorKind::Crlf => "crlf",
+            StyleErrorKind::Lf => "lf",
+            StyleErrorKind::NoBreak => "no-break",
+            StyleErrorKind::AnyBreak => "any-break",
+            StyleErrorKind::CrButNotLf => "cr-but-not-lf",
+            StyleErrorKind::NotEnoughIndentation => "not-enough-indentation",
+            StyleErrorKind::TooMuchIndentation => "too-much-indentation",
+            StyleErrorKind::Sorting => "sorting",
+            StyleErrorKind::ObjectKeyNaming => "object-key-naming",
+            StyleErrorKind::LeafKeyNaming => "leaf-key-naming",
+        }
+    }
+
+    pub fn description(&self, style: &JsonStyle) -> String {
+        let post_colon = match style {
+            JsonStyle::STYLED { post_colon: Some(post_colon), .. } => *post_colon,
+            _ => " ",
+        };
+
+        match self {
+            StyleErrorKind::MaybeSpace => "expected no whitespace here".to_string(),
+            StyleErrorKind::PostColon =>
+                format!("expected \"{}\" after the double colon", post_colon),
+            StyleErrorKind::PostColonTooMuch =>
+                format!("expected ONLY \"{}\" after the double colon", post_colon),
+            StyleErrorKind::Crlf => format!("expected \"{}\" at the end of a line", "\\r\\n".yellow()),
+            StyleErrorKind::Lf => format!("expected \"{}\" at the end of a line", "\\n".yellow()),
+            StyleErrorKind::NoBreak => "expected no linebreaks".to_string(),
+            StyleErrorKind::AnyBreak => "expected any linebreak at the end of a line".to_string(),
+            StyleErrorKind::CrButNotLf => format!("expected \"{}\" after \"{}\"", "\\n".yellow(), "\\r".yellow()),
+            StyleErrorKind::NotEnoughIndentation => "expected more indentation".to_string(),
+            StyleErrorKind::TooMuchIndentation => "expected less indentation".to_string(),
+            StyleErrorKind::Sorting => "expected the key to be greater than its predecessor".to_string(),
+            StyleErrorKind::ObjectKeyNaming => "expected the key of the object to follow the naming convention".to_string(),
+            StyleErrorKind::LeafKeyNaming => "expected the key of the string to follow the naming convention".to_string(),
+        }
+    }
+
+    pub fn message(&self, style: &JsonStyle, line: u32, column: usize) -> String {
+        format!(
+            "In line {} at offset {}, I {}",
+            format!("{}", line).blue(),
+            format!("{}", column).blue(),
+            self.description(style),
+        )
+    }
+}
+
+impl<'a> StyleError<'a> {
+    pub fn new(kind: StyleErrorKind, span: Span<'a>) -> StyleError<'a> {
+        StyleError {
+            kind,
+            span,
+        }
+    }
+}
+
+/// Runs `parser` on `s` and turns any failure into a style error of the given kind located at `s`.
+pub fn expect<'a, O, F>(kind: StyleErrorKind, mut parser: F, s: Span<'a>) -> Result<(Span<'a>, O), StyleError<'a>>
+    where
+        F: FnMut(Span<'a>) -> nom::IResult<Span<'a>, O, nom::error::Error<Span<'a>>>, {
+    parser(s).map_err(|_| StyleError::new(kind, s))
+}
@@ -1,5 +1,6 @@
-use crate::parser::model::{JsonObject, JsonString, JsonStyle, JsonType, LineEnding};
-use crate::{SortAlgorithm, SortOrder};
+use crate::checks::suppress::DIRECTIVE;
+use crate::parser::document::{JsonDocument, JsonValue};
+use crate::parser::model::{JsonStyle, LineEnding};
 
 pub struct Generator {
     style: JsonStyle
@@ -12,49 +13,37 @@ impl Generator {
         }
     }
 
-    pub fn generate(&self, mut json: JsonObject) -> String {
+    /// Generates the text of a parsed [`JsonObject`](crate::JsonObject) or an owned [`JsonDocument`].
+    pub fn generate(&self, json: impl Into<JsonDocument>) -> String {
+        let mut json = json.into();
         let mut buffer = String::new();
-        match self.style {
-            JsonStyle::STYLED { sort_algorithm, order, .. } => {
-                match sort_algorithm {
-                    SortAlgorithm::NATURAL => {
-                        match order {
-                            SortOrder::ASC => {
-                                json.sort_by(|a, b| {
-                                    crate::natural_sort::compare(a.value, b.value)
-                                })
-                            }
-                            SortOrder::DESC => {
-                                json.sort_by(|a, b| {
-                                    crate::natural_sort::compare(a.value, b.value).reverse()
-                                })
-                            }
-                        }
-                    }
-                    SortAlgorithm::NORMAL => {
-                        match order {
-                            SortOrder::ASC => {
-                                json.sort_by(|a, b| {
-                                    a.value.cmp(b.value)
-                                })
-                            }
-                            SortOrder::DESC => {
-                                json.sort_by(|a, b| {
-                                    a.value.cmp(b.value).reverse()
-                                })
-                            }
-                        }
-                    }
-                    SortAlgorithm::NONE => {}
-                }
-            }
-            JsonStyle::IGNORE => {}
-        }
+        self.sort(&mut json, "");
         self.generate_object(json, 0, &mut buffer);
 
         buffer
     }
 
+    /// Sorts the entries of the object at the dotted key path `path` and of its children, entries whose
+    /// order does not matter keep their order. Directives are placed first.
+    fn sort(&self, json: &mut JsonDocument, path: &str) {
+        json.values.sort_by(|(a, a_value), (b, b_value)| (a != DIRECTIVE).cmp(&(b != DIRECTIVE)).then_with(|| {
+            self.style.compare(
+                path,
+                (a, matches!(a_value, JsonValue::Object(_))),
+                (b, matches!(b_value, JsonValue::Object(_))),
+            )
+        }));
+        for (key, value) in &mut json.values {
+            if let JsonValue::Object(object) = value {
+                let path = match path {
+                    "" => key.clone(),
+                    _ => format!("{}.{}", path, key),
+                };
+                self.sort(object, &path);
+            }
+        }
+    }
+
     fn new_line(&self, buffer: &mut String) {
         match self.style {
             JsonStyle::STYLED { line_endings, .. } => {
@@ -85,7 +74,7 @@ impl Generator {
         }
     }
 
-    fn generate_object(&self, json: JsonObject, indent: u64, buffer: &mut String) {
+    fn generate_object(&self, json: JsonDocument, indent: u64, buffer: &mut String) {
         buffer.push('{');
         self.new_line(buffer);
         let mut values = json.values.into_iter().peekable();
@@ -101,9 +90,9 @@ impl Generator {
         buffer.push('}');
     }
 
-    fn generate_entry(&self, (key, value): (JsonString, JsonType), indent: u64, buffer: &mut String) {
+    fn generate_entry(&self, (key, value): (String, JsonValue), indent: u64, buffer: &mut String) {
         buffer.push('\"');
-        buffer.push_str(key.value);
+        buffer.push_str(&key);
         buffer.push('\"');
         buffer.push(':');
         match self.style {
@@ -117,24 +106,32 @@ impl Generator {
             JsonStyle::IGNORE => {}
         }
         match value {
-            JsonType::Object(object) => {
+            JsonValue::Object(object) => {
                 self.generate_object(object, indent, buffer);
             }
-            JsonType::String(string) => {
+            JsonValue::String(string) => {
                 buffer.push('\"');
-                buffer.push_str(string.value);
+                buffer.push_str(&string);
                 buffer.push('\"');
             }
         }
     }
 }
 
+
 #[cfg(test)]
 mod test {
-    use crate::parser::model::Span;
+    use std::sync::LazyLock;
+    use crate::parser::order::{Grouping, KeyOrder, PinnedKeys};
     use crate::{SortAlgorithm, SortOrder};
     use super::*;
 
+    fn stacked() -> JsonDocument {
+        JsonDocument::new()
+            .with("hello", "world")
+            .with("how", JsonDocument::new().with("are", "you"))
+    }
+
     #[test]
     fn generate_style_1_empty() {
         let generator = Generator {
@@ -144,13 +141,10 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
         };
-        let generated = generator.generate(JsonObject {
-            start: Span::new(""),
-            end: Span::new(""),
-            values: Vec::from([]),
-        });
+        let generated = generator.generate(JsonDocument::new());
 
         assert_eq!(generated, "{\n}")
     }
@@ -164,28 +158,62 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
         };
-        let generated = generator.generate(JsonObject {
-            start: Span::new(""),
-            end: Span::new(""),
-            values: Vec::from([
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "hello",
-                },
-                 JsonType::String(JsonString {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     value: "world",
-                 }))
-            ]),
-        });
+        let generated = generator.generate(JsonDocument::new().with("hello", "world"));
 
         assert_eq!(generated, "{\n    \"hello\": \"world\"\n}")
     }
 
+    #[test]
+    fn generate_collation_sorted() {
+        let generator = Generator {
+            style: JsonStyle::STYLED {
+                line_endings: LineEnding::LF,
+                indentation: Some(" "),
+                post_colon: Some(" "),
+                order: SortOrder::ASC,
+                sort_algorithm: SortAlgorithm::COLLATION,
+                key_order: None,
+            },
+        };
+        let generated = generator.generate(JsonDocument::new().with("Zebra", "z").with("éclair", "e").with("apple", "a"));
+
+        assert_eq!(generated, "{\n \"apple\": \"a\",\n \"éclair\": \"e\",\n \"Zebra\": \"z\"\n}")
+    }
+
+    #[test]
+    fn generate_key_order() {
+        static KEY_ORDER: LazyLock<KeyOrder> = LazyLock::new(|| KeyOrder {
+            pinned: vec![
+                PinnedKeys::parse("properties.*=type").unwrap(),
+                PinnedKeys::parse("$schema,title").unwrap(),
+            ],
+            grouping: Some(Grouping::StringsFirst),
+        });
+        let generator = Generator {
+            style: JsonStyle::STYLED {
+                line_endings: LineEnding::NONE,
+                indentation: Some(""),
+                post_colon: Some(""),
+                order: SortOrder::DESC,
+                sort_algorithm: SortAlgorithm::NORMAL,
+                key_order: Some(&KEY_ORDER),
+            },
+        };
+        let generated = generator.generate(JsonDocument::new()
+            .with("properties", JsonDocument::new().with("name", JsonDocument::new().with("title", "").with("type", "")))
+            .with("a", "")
+            .with("title", "")
+            .with("$schema", ""));
+
+        assert_eq!(
+            generated,
+            "{\"$schema\":\"\",\"title\":\"\",\"a\":\"\",\"properties\":{\"name\":{\"type\":\"\",\"title\":\"\"}}}"
+        )
+    }
+
     #[test]
     fn generate_style_1_stacked() {
         let generator = Generator {
@@ -195,45 +223,10 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
         };
-        let generated = generator.generate(JsonObject {
-            start: Span::new(""),
-            end: Span::new(""),
-            values: Vec::from([
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "hello",
-                },
-                 JsonType::String(JsonString {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     value: "world",
-                 })),
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "how",
-                },
-                 JsonType::Object(JsonObject {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     values: Vec::from([
-                         (JsonString {
-                             start: Span::new(""),
-                             end: Span::new(""),
-                             value: "are",
-                         },
-                          JsonType::String(JsonString {
-                              start: Span::new(""),
-                              end: Span::new(""),
-                              value: "you",
-                          })),
-                     ])
-                 }))
-            ]),
-        });
+        let generated = generator.generate(stacked());
 
         assert_eq!(
             generated,
@@ -250,45 +243,10 @@ mod test {
                 post_colon: Some(""),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
         };
-        let generated = generator.generate(JsonObject {
-            start: Span::new(""),
-            end: Span::new(""),
-            values: Vec::from([
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "hello",
-                },
-                 JsonType::String(JsonString {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     value: "world",
-                 })),
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "how",
-                },
-                 JsonType::Object(JsonObject {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     values: Vec::from([
-                         (JsonString {
-                             start: Span::new(""),
-                             end: Span::new(""),
-                             value: "are",
-                         },
-                          JsonType::String(JsonString {
-                              start: Span::new(""),
-                              end: Span::new(""),
-                              value: "you",
-                          })),
-                     ])
-                 }))
-            ]),
-        });
+        let generated = generator.generate(stacked());
 
         assert_eq!(
             generated,
@@ -305,49 +263,22 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
         };
-        let generated = generator.generate(JsonObject {
-            start: Span::new(""),
-            end: Span::new(""),
-            values: Vec::from([
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "hello",
-                },
-                 JsonType::String(JsonString {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     value: "world",
-                 })),
-                (JsonString {
-                    start: Span::new(""),
-                    end: Span::new(""),
-                    value: "how",
-                },
-                 JsonType::Object(JsonObject {
-                     start: Span::new(""),
-                     end: Span::new(""),
-                     values: Vec::from([
-                         (JsonString {
-                             start: Span::new(""),
-                             end: Span::new(""),
-                             value: "are",
-                         },
-                          JsonType::String(JsonString {
-                              start: Span::new(""),
-                              end: Span::new(""),
-                              value: "you",
-                          })),
-                     ])
-                 }))
-            ]),
-        });
+        let generated = generator.generate(stacked());
 
         assert_eq!(
             generated,
             "{\r\n  \"hello\": \"world\",\r\n  \"how\": {\r\n    \"are\": \"you\"\r\n  }\r\n}"
         )
     }
+
+    #[test]
+    fn generate_escaped() {
+        let generator = Generator::new(JsonStyle::IGNORE);
+        let generated = generator.generate(JsonDocument::new().with("a", "\"b\"\n"));
+
+        assert_eq!(generated, "{\"a\":\"\\\"b\\\"\\n\"}")
+    }
 }
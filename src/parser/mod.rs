@@ -1,3 +1,9 @@
 pub mod model;
+#[allow(clippy::module_inception)]
 pub mod parser;
 pub mod generator;
+pub mod error;
+pub mod document;
+pub mod path;
+pub mod naming;
+pub mod order;
@@ -1,6 +1,7 @@
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 use nom_locate::LocatedSpan;
+use crate::parser::order::KeyOrder;
 
 pub type Span<'a> = LocatedSpan<&'a str>;
 
@@ -12,10 +13,30 @@ pub enum JsonStyle {
         post_colon: Option<&'static str>,
         sort_algorithm: SortAlgorithm,
         order: SortOrder,
+        /// Pinned keys and grouping by type, applied before the sort algorithm
+        key_order: Option<&'static KeyOrder>,
     },
     IGNORE
 }
 
+impl JsonStyle {
+    /// Compares two entries of the object at the dotted path `object` in the expected order, given as
+    /// their key and whether their value is an object. Entries are equal if their order does not matter.
+    pub fn compare(&self, object: &str, a: (&str, bool), b: (&str, bool)) -> Ordering {
+        match self {
+            JsonStyle::STYLED { sort_algorithm, order, key_order, .. } => {
+                let sorted = sort_algorithm.compare(a.0, b.0);
+                let sorted = match order {
+                    SortOrder::ASC => sorted,
+                    SortOrder::DESC => sorted.reverse(),
+                };
+                key_order.map_or(Ordering::Equal, |key_order| key_order.compare(object, a, b)).then(sorted)
+            }
+            JsonStyle::IGNORE => Ordering::Equal,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum SortOrder {
     ASC,
@@ -23,12 +44,33 @@ pub enum SortOrder {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[allow(non_camel_case_types)]
 pub enum SortAlgorithm {
     NATURAL,
     NORMAL,
+    /// Compares the lowercase keys
+    CASE_INSENSITIVE,
+    /// Compares the lowercase keys by natural sort
+    NATURAL_CASE_INSENSITIVE,
+    /// The Unicode Collation Algorithm with the CLDR root order, e.g. `apple`, `éclair`, `Zebra`
+    COLLATION,
     NONE,
 }
 
+impl SortAlgorithm {
+    /// Compares two keys, every key is equal if the keys are not sorted.
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match self {
+            SortAlgorithm::NATURAL => crate::natural_sort::compare(a, b),
+            SortAlgorithm::NORMAL => a.cmp(b),
+            SortAlgorithm::CASE_INSENSITIVE => crate::collation::case_insensitive(a, b),
+            SortAlgorithm::NATURAL_CASE_INSENSITIVE => crate::collation::natural_case_insensitive(a, b),
+            SortAlgorithm::COLLATION => crate::collation::collate(a, b),
+            SortAlgorithm::NONE => Ordering::Equal,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum LineEnding {
     CRLF,
@@ -44,6 +86,16 @@ pub enum JsonType<'a> {
     String(JsonString<'a>)
 }
 
+impl<'a> JsonType<'a> {
+    /// The start and the end of the value.
+    pub fn span(&self) -> (Span<'a>, Span<'a>) {
+        match self {
+            JsonType::Object(object) => (object.start, object.end),
+            JsonType::String(string) => (string.start, string.end),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct JsonObject<'a> {
     pub values: Vec<(JsonString<'a>, JsonType<'a>)>,
@@ -98,6 +150,7 @@ impl <'s>PartialEq for JsonString<'s> {
 impl <'s>Eq for JsonString<'s> {
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl <'s>PartialOrd for JsonString<'s> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.value.partial_cmp(other.value)
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use regex::Regex;
+use crate::checks::suppress::DIRECTIVE;
+use crate::parser::document::{JsonDocument, JsonValue};
+
+/// A naming convention for keys.
+#[derive(Debug, Clone)]
+pub enum KeyCase {
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `camelCase`
+    Camel,
+    /// `kebab-case`
+    Kebab,
+    /// Keys matching the regex
+    Custom(Regex),
+}
+
+/// The naming conventions of the keys of objects and of the keys of strings, the leaves.
+#[derive(Debug, Clone, Default)]
+pub struct KeyNaming {
+    pub objects: Option<KeyCase>,
+    pub leaves: Option<KeyCase>,
+}
+
+/// The new names of keys, by the key path of their object and their old name. The paths consist of the old names.
+pub type Renames = BTreeMap<(Vec<String>, String), String>;
+
+/// Splits `key` into its words at `_`, `-`, spaces and the humps of camel case.
+fn words(key: &str) -> Vec<String> {
+    let chars = key.chars().collect::<Vec<_>>();
+    let mut words = vec![String::new()];
+    for (index, c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ') {
+            words.push(String::new());
+            continue;
+        }
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let hump = c.is_uppercase() && previous.is_some_and(|previous| {
+            previous.is_lowercase()
+                || previous.is_numeric()
+                || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+        });
+        if hump {
+            words.push(String::new());
+        }
+        words.last_mut().unwrap().push(*c);
+    }
+    words.retain(|word| !word.is_empty());
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars.next()
+        .map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+        .unwrap_or_default()
+}
+
+impl KeyCase {
+    fn separator(&self) -> Option<char> {
+        match self {
+            KeyCase::Snake | KeyCase::ScreamingSnake => Some('_'),
+            KeyCase::Kebab => Some('-'),
+            KeyCase::Camel | KeyCase::Custom(_) => None,
+        }
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            KeyCase::Custom(regex) => regex.is_match(key),
+            _ => key.chars().all(|c| c.is_alphanumeric() || Some(c) == self.separator())
+                && self.convert(key).as_deref() == Some(key),
+        }
+    }
+
+    /// Converts `key` to this naming convention, a custom regex can not be converted to.
+    pub fn convert(&self, key: &str) -> Option<String> {
+        let words = words(key);
+        match self {
+            KeyCase::Snake => Some(words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("_")),
+            KeyCase::ScreamingSnake => Some(words.iter().map(|word| word.to_uppercase()).collect::<Vec<_>>().join("_")),
+            KeyCase::Kebab => Some(words.iter().map(|word| word.to_lowercase()).collect::<Vec<_>>().join("-")),
+            KeyCase::Camel => Some(words.iter().enumerate()
+                .map(|(index, word)| if index == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect()),
+            KeyCase::Custom(_) => None,
+        }
+    }
+}
+
+/// The keys of every object by its key path, with the new name of the key in each document it occurs in.
+type Candidates = BTreeMap<Vec<String>, BTreeMap<String, Vec<Option<String>>>>;
+
+impl KeyNaming {
+    /// Renames the keys of `document` which do not follow the naming conventions, like [`KeyNaming::renames`]
+    /// for a single document.
+    pub fn fix(&self, document: &mut JsonDocument) {
+        let renames = self.renames(&[&*document]);
+        rename(&renames, document);
+    }
+
+    /// The renames of the keys which do not follow the naming conventions in `documents`, the files of a group,
+    /// so a key gets the same name in every file. A key is kept in every document if it can not be converted,
+    /// it would be renamed differently in one of them or the renamed key already exists in one of them.
+    /// Directives are never renamed.
+    pub fn renames(&self, documents: &[&JsonDocument]) -> Renames {
+        let mut candidates = Candidates::new();
+        for document in documents {
+            self.candidates(document, &[], &mut candidates);
+        }
+
+        let mut renames = Renames::new();
+        for (path, keys) in candidates {
+            let mut renamed = BTreeMap::new();
+            for (key, names) in &keys {
+                if let [Some(name), others @ ..] = names.as_slice() {
+                    if others.iter().all(|other| other.as_ref() == Some(name)) && !keys.contains_key(name) {
+                        renamed.insert(key.clone(), name.clone());
+                    }
+                }
+            }
+            // Two keys with the same new name would collide as well
+            let mut names = BTreeSet::new();
+            let collisions = renamed.values()
+                .filter(|name| !names.insert(*name))
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            for (key, name) in renamed {
+                if !collisions.contains(&name) {
+                    renames.insert((path.clone(), key), name);
+                }
+            }
+        }
+
+        renames
+    }
+
+    fn candidates(&self, document: &JsonDocument, path: &[String], candidates: &mut Candidates) {
+        for (key, value) in &document.values {
+            let case = match value {
+                JsonValue::Object(_) => &self.objects,
+                JsonValue::String(_) => &self.leaves,
+            };
+            let renamed = case.as_ref()
+                .filter(|case| key != DIRECTIVE && !case.matches(key))
+                .and_then(|case| case.convert(key));
+            candidates.entry(path.to_vec()).or_default()
+                .entry(key.clone()).or_default()
+                .push(renamed);
+            if let JsonValue::Object(object) = value {
+                let mut path = path.to_vec();
+                path.push(key.clone());
+                self.candidates(object, &path, candidates);
+            }
+        }
+    }
+}
+
+/// Applies the `renames` to `document` and returns whether a key was renamed.
+pub fn rename(renames: &Renames, document: &mut JsonDocument) -> bool {
+    rename_at(renames, document, &[])
+}
+
+fn rename_at(renames: &Renames, document: &mut JsonDocument, path: &[String]) -> bool {
+    let mut renamed = false;
+    for (key, value) in &mut document.values {
+        let old = key.clone();
+        if let JsonValue::Object(object) = value {
+            let mut path = path.to_vec();
+            path.push(old.clone());
+            renamed |= rename_at(renames, object, &path);
+        }
+        if let Some(name) = renames.get(&(path.to_vec(), old)) {
+            *key = name.clone();
+            renamed = true;
+        }
+    }
+
+    renamed
+}
+
+impl FromStr for KeyCase {
+    type Err = String;
+
+    /// Parses `snake`, `screaming-snake`, `camel`, `kebab` or `regex:<regex>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake" => Ok(KeyCase::Snake),
+            "screaming-snake" => Ok(KeyCase::ScreamingSnake),
+            "camel" => Ok(KeyCase::Camel),
+            "kebab" => Ok(KeyCase::Kebab),
+            _ => match s.strip_prefix("regex:") {
+                Some(regex) => Regex::new(regex).map(KeyCase::Custom).map_err(|e| e.to_string()),
+                None => Err("expected snake, screaming-snake, camel, kebab or regex:<regex>".to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn convert_cases() {
+        for key in ["hide_intro", "HIDE_INTRO", "hideIntro", "HideIntro", "hide-intro"] {
+            assert_eq!(KeyCase::Snake.convert(key).unwrap(), "hide_intro");
+            assert_eq!(KeyCase::ScreamingSnake.convert(key).unwrap(), "HIDE_INTRO");
+            assert_eq!(KeyCase::Camel.convert(key).unwrap(), "hideIntro");
+            assert_eq!(KeyCase::Kebab.convert(key).unwrap(), "hide-intro");
+        }
+        assert_eq!(KeyCase::Snake.convert("parseHTMLFile2").unwrap(), "parse_html_file2");
+    }
+
+    #[test]
+    fn fix_document() {
+        let naming = KeyNaming {
+            objects: Some(KeyCase::Camel),
+            leaves: Some(KeyCase::ScreamingSnake),
+        };
+        let mut document = JsonDocument::new()
+            .with("shared_actions", JsonDocument::new().with("markRead", "1").with("MARK_READ", "2"))
+            .with("introduction", "3");
+        naming.fix(&mut document);
+
+        assert_eq!(document, JsonDocument::new()
+            .with("sharedActions", JsonDocument::new().with("markRead", "1").with("MARK_READ", "2"))
+            .with("INTRODUCTION", "3"));
+    }
+
+    #[test]
+    fn rename_a_group() {
+        let naming = KeyNaming {
+            objects: None,
+            leaves: Some(KeyCase::Snake),
+        };
+        let de = JsonDocument::new().with("saveButton", "1").with("cancelButton", "2").with("cancel_button", "3");
+        let mut en = JsonDocument::new().with("saveButton", "4").with("cancelButton", "5");
+        let renames = naming.renames(&[&de, &en]);
+
+        // cancelButton would collide in de, so it is kept in en as well
+        assert_eq!(renames, Renames::from([((Vec::new(), "saveButton".to_string()), "save_button".to_string())]));
+        assert!(rename(&renames, &mut en));
+        assert_eq!(en, JsonDocument::new().with("save_button", "4").with("cancelButton", "5"));
+        assert!(!rename(&renames, &mut JsonDocument::new().with("save_button", "6")));
+    }
+
+    #[test]
+    fn match_cases() {
+        assert!(KeyCase::Snake.matches("hide_intro2"));
+        assert!(!KeyCase::Snake.matches("hideIntro"));
+        assert!(!KeyCase::Snake.matches("hide__intro"));
+        assert!(KeyCase::ScreamingSnake.matches("HIDE_INTRO"));
+        assert!(!KeyCase::ScreamingSnake.matches("introduction"));
+        assert!(KeyCase::Camel.matches("introduction"));
+        assert!(KeyCase::Camel.matches("hideIntro"));
+        assert!(!KeyCase::Camel.matches("HideIntro"));
+        assert!(KeyCase::Kebab.matches("hide-intro"));
+        assert!(!KeyCase::Kebab.matches("hide.intro"));
+
+        let custom = "regex:^[a-z]+$".parse::<KeyCase>().unwrap();
+        assert!(custom.matches("intro"));
+        assert!(!custom.matches("Intro"));
+        assert!(custom.convert("Intro").is_none());
+        assert!("pascal".parse::<KeyCase>().is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use glob::Pattern;
+
+/// Where the entries of an object are placed depending on the type of their value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// The objects come after the strings
+    StringsFirst,
+    /// The strings come after the objects
+    ObjectsFirst,
+}
+
+/// Keys which come first in the given order, in the objects whose dotted key path matches the glob or
+/// in every object.
+#[derive(Debug, Clone)]
+pub struct PinnedKeys {
+    pub objects: Option<Pattern>,
+    pub keys: Vec<String>,
+}
+
+/// An ordering of the keys applied before the sort algorithm, which only orders the remaining keys.
+#[derive(Debug, Clone, Default)]
+pub struct KeyOrder {
+    /// The first rule matching an object applies to it
+    pub pinned: Vec<PinnedKeys>,
+    pub grouping: Option<Grouping>,
+}
+
+impl FromStr for Grouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strings-first" => Ok(Grouping::StringsFirst),
+            "objects-first" => Ok(Grouping::ObjectsFirst),
+            _ => Err(format!("expected strings-first or objects-first but found \"{}\"", s)),
+        }
+    }
+}
+
+impl PinnedKeys {
+    /// Parses keys written as `[<glob>=]<key>,<key>,...`, e.g. `$schema,$id` or `properties.*=type,title`.
+    pub fn parse(rule: &str) -> Result<PinnedKeys, String> {
+        let (objects, keys) = match rule.split_once('=') {
+            Some((objects, keys)) => (Some(Pattern::new(objects).map_err(|e| e.to_string())?), keys),
+            None => (None, rule),
+        };
+        let keys = keys.split(',')
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            return Err(format!("expected [<glob>=]<key>,<key>,... but found \"{}\"", rule));
+        }
+
+        Ok(PinnedKeys { objects, keys })
+    }
+}
+
+impl KeyOrder {
+    /// The position of `key` among the pinned keys of the object at the dotted path `object`.
+    fn rank(&self, object: &str, key: &str) -> Option<usize> {
+        self.pinned.iter()
+            .find(|pinned| pinned.objects.as_ref().is_none_or(|objects| objects.matches(object)))
+            .and_then(|pinned| pinned.keys.iter().position(|pinned| pinned == key))
+    }
+
+    /// Compares two entries of the object at the dotted path `object`, the root being the empty path.
+    /// The entries are given as their key and whether their value is an object. Entries which are
+    /// neither grouped apart nor pinned are equal.
+    pub fn compare(&self, object: &str, (a, a_object): (&str, bool), (b, b_object): (&str, bool)) -> Ordering {
+        let grouping = match self.grouping {
+            Some(Grouping::StringsFirst) => a_object.cmp(&b_object),
+            Some(Grouping::ObjectsFirst) => b_object.cmp(&a_object),
+            None => Ordering::Equal,
+        };
+
+        grouping.then_with(|| match (self.rank(object, a), self.rank(object, b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pinned_and_grouped() {
+        let order = KeyOrder {
+            pinned: vec![
+                PinnedKeys::parse("properties.*=type,title").unwrap(),
+                PinnedKeys::parse("$schema, $id,title,type").unwrap(),
+            ],
+            grouping: Some(Grouping::StringsFirst),
+        };
+
+        assert_eq!(order.compare("", ("$id", false), ("$schema", false)), Ordering::Greater);
+        assert_eq!(order.compare("", ("type", false), ("definitions", false)), Ordering::Less);
+        assert_eq!(order.compare("", ("$schema", true), ("default", false)), Ordering::Greater);
+        assert_eq!(order.compare("", ("default", false), ("pattern", false)), Ordering::Equal);
+        assert_eq!(order.compare("properties.name", ("type", false), ("title", false)), Ordering::Less);
+        assert!(PinnedKeys::parse("properties=").is_err());
+        assert!("strings-last".parse::<Grouping>().is_err());
+    }
+}
@@ -2,97 +2,39 @@ use std::cmp::Ordering;
 use nom::{bytes::complete::{tag, take}, character::complete::{multispace0, one_of}, Finish, InputTake, IResult};
 use nom::character::complete::none_of;
 use nom::combinator::{not, opt};
-use nom::error::{context, ErrorKind, ParseError, VerboseError, VerboseErrorKind};
+use nom::error::VerboseError;
 use nom_locate::position;
 
-use crate::parser::model::{JsonObject, JsonString, JsonStyle, JsonType, LineEnding, SortOrder, Span};
-use crate::SortAlgorithm;
-
-mod error {
-    use colored::Colorize;
-    use crate::parser::model::{JsonStyle, Span};
-
-    pub const MAYBE_SPACE: &str = "maybe_space";
-    pub const POST_COLON: &str = "post_colon";
-    pub const POST_COLON_TOO_MUCH: &str = "post_colon_too_much";
-    pub const CRLF: &str = "crlf";
-    pub const LF: &str = "lf";
-    pub const NO_BREAK: &str = "no_break";
-    pub const ANY_BREAK: &str = "any_break";
-    pub const CR_BUT_NOT_LF: &str = "cr_but_no_lf";
-    pub const NOT_ENOUGH_INDENTATION: &str = "not_enough_indentation";
-    pub const TOO_MUCH_INDENTATION: &str = "too_much_indentation";
-    pub const SORTING: &str = "sorting";
-
-    pub fn context_to_message(style: &JsonStyle, context: &str) -> Option<String> {
-        match context {
-            MAYBE_SPACE => Some(format!("expected no whitespace here")),
-            POST_COLON => match style {
-                JsonStyle::STYLED { post_colon, .. } =>
-                    Some(format!("expected \"{}\" after the double colon", match post_colon {
-                        Some(s) => *s,
-                        None => " ",
-                    })),
-                JsonStyle::IGNORE => None,
-            },
-            POST_COLON_TOO_MUCH => match style {
-                JsonStyle::STYLED { post_colon, .. } =>
-                    Some(format!("expected ONLY \"{}\" after the double colon", match post_colon {
-                        Some(s) => *s,
-                        None => " ",
-                    })),
-                JsonStyle::IGNORE => None,
-            },
-            CRLF => Some(format!("expected \"{}\" at the end of a line", "\\r\\n".yellow())),
-            LF => Some(format!("expected \"{}\" at the end of a line", "\\n".yellow())),
-            NO_BREAK => Some(format!("expected no linebreaks")),
-            ANY_BREAK => Some(format!("expected any linebreak at the end of a line")),
-            CR_BUT_NOT_LF => Some(format!("expected \"{}\" after \"{}\"", "\\n".yellow(), "\\r".yellow())),
-            NOT_ENOUGH_INDENTATION => Some(format!("expected more indentation")),
-            TOO_MUCH_INDENTATION => Some(format!("expected less indentation")),
-            SORTING => Some(format!("expected the key to be greater than its predecessor")),
-            _ => None,
-        }
-    }
+use crate::checks::suppress::DIRECTIVE;
+use crate::parser::error::{expect, StyleError, StyleErrorKind};
+use crate::parser::model::{JsonObject, JsonString, JsonStyle, JsonType, LineEnding, Span};
+use crate::parser::naming::KeyNaming;
 
-    pub fn generate_error_message<'b>(
-        style: &JsonStyle,
-        err: &nom::error::VerboseError<Span<'b>>,
-    ) -> Option<(Span<'b>, String)> {
-        for (s, e) in &err.errors {
-            if let nom::error::VerboseErrorKind::Context(ctx) = e {
-                if let Some(message) = context_to_message(style, ctx) {
-                    return Some((
-                        s.clone(),
-                        format!(
-                            "In line {} at offset {}, I {}",
-                            format!("{}", s.location_line()).blue(),
-                            format!("{}", s.get_column()).blue(),
-                            message,
-                        ),
-                    ))
-                }
-            }
-        }
-
-        None
-    }
-}
+pub type ParseResult<'a> = Result<(JsonObject<'a>, Vec<StyleError<'a>>), VerboseError<Span<'a>>>;
 
 pub struct Parser {
-    style: JsonStyle
+    style: JsonStyle,
+    naming: KeyNaming,
 }
 
 impl Parser {
     pub fn new(style: JsonStyle) -> Parser {
         Parser {
             style,
+            naming: KeyNaming::default(),
         }
     }
 
+    /// Additionally checks the keys against the naming conventions.
+    pub fn with_naming(mut self, naming: KeyNaming) -> Parser {
+        self.naming = naming;
+        self
+    }
+
+    #[allow(clippy::collapsible_else_if)]
     fn parse_string(
         s: Span,
-    ) -> IResult<Span, JsonString, nom::error::VerboseError<Span>> {
+    ) -> IResult<Span, JsonString, VerboseError<Span>> {
         let (s, _) = tag("\"")(s)?;
         let (s, start) = position(s)?;
         let mut ms = s;
@@ -132,7 +74,7 @@ impl Parser {
     #[inline(always)]
     fn parse_space0(
         mut s: Span,
-    ) -> IResult<Span, Span, nom::error::VerboseError<Span>> {
+    ) -> IResult<Span, Span, VerboseError<Span>> {
         while let Ok((sn, _)) = one_of::<_, _, nom::error::Error<Span>>(" \t")(s) {
             s = sn
         }
@@ -141,78 +83,59 @@ impl Parser {
     }
 
     #[inline(always)]
-    fn parse_maybe_space<'a, 'b>(
-        &'a self,
+    fn parse_maybe_space<'b>(
+        &self,
         s: Span<'b>,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
-    ) -> IResult<Span<'b>, Span<'b>, nom::error::VerboseError<Span<'b>>> {
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, Span<'b>, VerboseError<Span<'b>>> {
         match match self.style {
-            JsonStyle::STYLED { .. } => context(error::MAYBE_SPACE, not(one_of(" \t")))(s).map(|(_, _)| s),
-            JsonStyle::IGNORE => multispace0(s).map(|(s, _)| s),
+            JsonStyle::STYLED { .. } => expect(StyleErrorKind::MaybeSpace, not(one_of(" \t")), s).map(|_| s),
+            JsonStyle::IGNORE => Ok(multispace0(s)?.0),
         } {
             Ok(s) => {
                 Ok((s, Span::new("")))
             },
             Err(e) => {
-                match e {
-                    nom::Err::Incomplete(_) => {
-                        // Not happening
-                        std::process::exit(-1);
-                    }
-                    nom::Err::Error(e) | nom::Err::Failure(e) => {
-                        style_errors.push(e);
-                        let (s, _) = Parser::parse_space0(s)?;
-                        Ok((s, Span::new("")))
-                    }
-                }
+                style_errors.push(e);
+                let (s, _) = Parser::parse_space0(s)?;
+                Ok((s, Span::new("")))
             }
         }
     }
 
     #[inline(always)]
-    fn parse_post_colon<'a, 'b>(
-        &'a self,
+    fn parse_post_colon<'b>(
+        &self,
         s: Span<'b>,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
-    ) -> IResult<Span<'b>, Span<'b>, nom::error::VerboseError<Span<'b>>> {
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, Span<'b>, VerboseError<Span<'b>>> {
         match match self.style {
-            JsonStyle::STYLED { post_colon, .. } => {
-                if let Some(post_colon) = post_colon {
-                    match context(error::POST_COLON, tag(post_colon))(s).map(|(s, _)| s) {
-                        Ok(s) => context(error::POST_COLON_TOO_MUCH, one_of("{\""))(s).map(|_| s),
-                        Err(e) => Err(e),
-                    }
-                } else {
-                    multispace0(s).map(|(s, _)| s)
+            JsonStyle::STYLED { post_colon: Some(post_colon), .. } => {
+                match expect(StyleErrorKind::PostColon, tag(post_colon), s) {
+                    Ok((s, _)) => expect(StyleErrorKind::PostColonTooMuch, one_of("{\""), s).map(|_| s),
+                    Err(e) => Err(e),
                 }
             }
-            JsonStyle::IGNORE => multispace0(s).map(|(s, _)| s),
+            JsonStyle::STYLED { post_colon: None, .. } | JsonStyle::IGNORE => Ok(multispace0(s)?.0),
         } {
             Ok(s) => {
                 Ok((s, Span::new("")))
             },
             Err(e) => {
-                match e {
-                    nom::Err::Incomplete(_) => {
-                        // Not happening
-                        std::process::exit(-1);
-                    }
-                    nom::Err::Error(e) | nom::Err::Failure(e) => {
-                        style_errors.push(e);
-                        let (s, _) = multispace0(s)?;
-                        Ok((s, Span::new("")))
-                    }
-                }
+                style_errors.push(e);
+                let (s, _) = multispace0(s)?;
+                Ok((s, Span::new("")))
             }
         }
     }
 
-    fn parse_entry<'a, 'b>(
-        &'a self,
+    fn parse_entry<'b>(
+        &self,
         s: Span<'b>,
         indent: u64,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
-    ) -> IResult<Span<'b>, (JsonString<'b>, JsonType<'b>), nom::error::VerboseError<Span<'b>>> {
+        path: &str,
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, (JsonString<'b>, JsonType<'b>), VerboseError<Span<'b>>> {
         let (s, key) = Parser::parse_string(s)?;
         let (s, _) = self.parse_maybe_space(s, style_errors)?;
         let (s, _) = tag(":")(s)?;
@@ -221,132 +144,135 @@ impl Parser {
 
         if c == '\"' {
             let (s, value) = Parser::parse_string(s)?;
+            if key.value != DIRECTIVE && self.naming.leaves.as_ref().is_some_and(|case| !case.matches(key.value)) {
+                style_errors.push(StyleError::new(StyleErrorKind::LeafKeyNaming, key.start));
+            }
 
             Ok((s, (key, JsonType::String(value))))
         } else {
-            let (s, value) = self.parse_object(s, indent, style_errors)?;
+            if self.naming.objects.as_ref().is_some_and(|case| !case.matches(key.value)) {
+                style_errors.push(StyleError::new(StyleErrorKind::ObjectKeyNaming, key.start));
+            }
+            let path = match path {
+                "" => key.value.to_string(),
+                _ => format!("{}.{}", path, key.value),
+            };
+            let (s, value) = self.parse_object_at(s, indent, &path, style_errors)?;
 
             Ok((s, (key, JsonType::Object(value))))
         }
     }
 
     #[inline(always)]
-    fn parse_new_line<'a, 'b>(
-        &'a self,
+    fn parse_new_line<'b>(
+        &self,
         s: Span<'b>,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
-    ) -> IResult<Span<'b>, Span<'b>, nom::error::VerboseError<Span<'b>>> {
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, Span<'b>, VerboseError<Span<'b>>> {
         match match self.style {
             JsonStyle::STYLED { line_endings, .. } => {
                 match line_endings {
-                    LineEnding::CRLF => context(error::CRLF, tag("\r\n"))(s).map(|(s, _)| s),
-                    LineEnding::LF => context(error::LF, tag("\n"))(s).map(|(s, _)| s),
-                    LineEnding::NONE => context(error::NO_BREAK, none_of("\r\n"))(s).map(|(_, _)| s),
+                    LineEnding::CRLF => expect(StyleErrorKind::Crlf, tag("\r\n"), s).map(|(s, _)| s),
+                    LineEnding::LF => expect(StyleErrorKind::Lf, tag("\n"), s).map(|(s, _)| s),
+                    LineEnding::NONE => expect(StyleErrorKind::NoBreak, none_of("\r\n"), s).map(|_| s),
                     LineEnding::ANY => {
                         let (s, _) = opt(tag("\r"))(s)?;
-                        context(error::ANY_BREAK, tag("\n"))(s).map(|(s, _)| s)
+                        expect(StyleErrorKind::AnyBreak, tag("\n"), s).map(|(s, _)| s)
                     }
                     LineEnding::IGNORE => {
                         let (s, v) = opt(tag("\r"))(s)?;
                         match v {
-                            Some(_) => context(error::CR_BUT_NOT_LF, tag("\n"))(s).map(|(s, _)| s),
-                            None => opt(tag("\n"))(s).map(|(s, _)| s),
+                            Some(_) => expect(StyleErrorKind::CrButNotLf, tag("\n"), s).map(|(s, _)| s),
+                            None => Ok(opt(tag("\n"))(s)?.0),
                         }
                     }
                 }
             }
-            JsonStyle::IGNORE => multispace0(s).map(|(s, _)| s),
+            JsonStyle::IGNORE => Ok(multispace0(s)?.0),
         } {
             Ok(s) => {
                 Ok((s, Span::new("")))
             },
             Err(e) => {
-                match e {
-                    nom::Err::Incomplete(_) => {
-                        // Not happening
-                        std::process::exit(-1);
-                    }
-                    nom::Err::Error(e) | nom::Err::Failure(e) => {
-                        style_errors.push(e);
-                        let (s, v) = opt(tag("\r"))(s)?;
-                        let s = match v {
-                            Some(_) => tag("\n")(s)?.0,
-                            None => opt(tag("\n"))(s)?.0,
-                        };
-                        Ok((s, Span::new("")))
-                    }
-                }
+                style_errors.push(e);
+                let (s, v) = opt(tag("\r"))(s)?;
+                let s = match v {
+                    Some(_) => tag("\n")(s)?.0,
+                    None => opt(tag("\n"))(s)?.0,
+                };
+                Ok((s, Span::new("")))
             }
         }
     }
 
     #[inline(always)]
-    fn parse_indentation<'a, 'b>(
-        &'a self,
+    fn parse_indentation<'b>(
+        &self,
         s: Span<'b>,
         indent: u64,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
+        style_errors: &mut Vec<StyleError<'b>>,
         last: bool,
-    ) -> IResult<Span<'b>, Span<'b>, nom::error::VerboseError<Span<'b>>> {
+    ) -> IResult<Span<'b>, Span<'b>, VerboseError<Span<'b>>> {
         match match self.style {
-            JsonStyle::STYLED { indentation, .. } => {
-                if let Some(indentation) = indentation {
-                    let mut s = Ok(s);
-                    for _ in 0..indent {
-                        if let Ok(s_ok) = s {
-                            s = context(
-                                error::NOT_ENOUGH_INDENTATION,
-                                tag(indentation)
-                            )(s_ok).map(|(s, _)| s);
-                        } else {
-                            break;
-                        }
+            JsonStyle::STYLED { indentation: Some(indentation), .. } => {
+                let mut s = Ok(s);
+                for _ in 0..indent {
+                    if let Ok(s_ok) = s {
+                        s = expect(
+                            StyleErrorKind::NotEnoughIndentation,
+                            tag(indentation),
+                            s_ok,
+                        ).map(|(s, _)| s);
+                    } else {
+                        break;
                     }
+                }
 
-                    if last {
-                        match s {
-                            Ok(s) => {
-                                context(
-                                    error::TOO_MUCH_INDENTATION,
-                                    one_of("\"}")
-                                )(s).map(|_| s)
-                            },
-                            Err(e) => Err(e),
-                        }
-                    } else {
-                        s
+                if last {
+                    match s {
+                        Ok(s) => {
+                            expect(
+                                StyleErrorKind::TooMuchIndentation,
+                                one_of("\"}"),
+                                s,
+                            ).map(|_| s)
+                        },
+                        Err(e) => Err(e),
                     }
                 } else {
-                    multispace0(s).map(|(s, _)| s)
+                    s
                 }
             }
-            JsonStyle::IGNORE => multispace0(s).map(|(s, _)| s),
+            JsonStyle::STYLED { indentation: None, .. } | JsonStyle::IGNORE => Ok(multispace0(s)?.0),
         } {
             Ok(s) => {
                 Ok((s, Span::new("")))
             },
             Err(e) => {
-                match e {
-                    nom::Err::Incomplete(_) => {
-                        // Not happening
-                        std::process::exit(-1);
-                    }
-                    nom::Err::Error(e) | nom::Err::Failure(e) => {
-                        style_errors.push(e);
-                        let (s, _) = Parser::parse_space0(s)?;
-                        Ok((s, Span::new("")))
-                    }
-                }
+                style_errors.push(e);
+                let (s, _) = Parser::parse_space0(s)?;
+                Ok((s, Span::new("")))
             }
         }
     }
 
-    fn parse_object<'a, 'b>(
-        &'a self,
+    fn parse_object<'b>(
+        &self,
+        s: Span<'b>,
+        indent: u64,
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, JsonObject<'b>, VerboseError<Span<'b>>> {
+        self.parse_object_at(s, indent, "", style_errors)
+    }
+
+    /// Parses the object at the dotted key path `path`, which the key order may depend on.
+    fn parse_object_at<'b>(
+        &self,
         s: Span<'b>,
         indent: u64,
-        style_errors: &mut Vec<nom::error::VerboseError<Span<'b>>>,
-    ) -> IResult<Span<'b>, JsonObject<'b>, nom::error::VerboseError<Span<'b>>> {
+        path: &str,
+        style_errors: &mut Vec<StyleError<'b>>,
+    ) -> IResult<Span<'b>, JsonObject<'b>, VerboseError<Span<'b>>> {
         let (s, _) = tag("{")(s)?;
         let (s, start) = position(s)?;
         let (s, _) = self.parse_new_line(s, style_errors)?;
@@ -367,36 +293,17 @@ impl Parser {
             let mut values: Vec<(JsonString, JsonType)> = Vec::new();
             let mut ms= s;
             loop {
-                let (s, kv) = self.parse_entry(ms, indent + 1, style_errors)?;
-                if let Some((key, _)) = values.last() {
-                    match self.style {
-                        JsonStyle::STYLED { order, sort_algorithm, .. } => {
-                            match (order, match sort_algorithm {
-                                SortAlgorithm::NATURAL => {
-                                    crate::natural_sort::compare(kv.0.value, key.value)
-                                }
-                                SortAlgorithm::NORMAL => {
-                                    kv.0.value.cmp(key.value)
-                                }
-                                SortAlgorithm::NONE => {
-                                    Ordering::Greater
-                                }
-                            }) {
-                                (SortOrder::ASC, Ordering::Less) | (SortOrder::DESC, Ordering::Greater) => {
-                                    let mut e = VerboseError::from_error_kind(
-                                        kv.0.start.clone(),
-                                        ErrorKind::Verify
-                                    );
-                                    e.errors.push((
-                                        kv.0.start.clone(),
-                                        VerboseErrorKind::Context(error::SORTING)
-                                    ));
-                                    style_errors.push(e);
-                                }
-                                _ => {}
-                            }
-                        }
-                        JsonStyle::IGNORE => {}
+                let (s, kv) = self.parse_entry(ms, indent + 1, path, style_errors)?;
+                // The directive may be placed anywhere
+                let previous = values.iter().rev().find(|(key, _)| key.value != DIRECTIVE);
+                if let (false, Some((key, value))) = (kv.0.value == DIRECTIVE, previous) {
+                    let previous = (key.value, matches!(value, JsonType::Object(_)));
+                    let current = (kv.0.value, matches!(kv.1, JsonType::Object(_)));
+                    if self.style.compare(path, current, previous) == Ordering::Less {
+                        style_errors.push(StyleError::new(
+                            StyleErrorKind::Sorting,
+                            kv.0.start,
+                        ));
                     }
                 }
 
@@ -418,31 +325,31 @@ impl Parser {
 
             let (ms, end) = position(ms)?;
             Ok((ms, JsonObject {
-                values: values,
+                values,
                 start,
                 end,
             }))
         }
     }
 
-    pub fn parse<'a, 'b>(&'a self, s: &'b str) -> Result<(JsonObject<'b>, Vec<(Span<'b>, String)>), nom::error::VerboseError<Span<'b>>> {
+    /// Parses `s` and collects every distinct style violation, ordered by its position in the input.
+    pub fn parse<'b>(&self, s: &'b str) -> ParseResult<'b> {
         let mut style_errors = Vec::new();
         let (_, json) = self.parse_object(Span::new(s), 0, &mut style_errors).finish()?;
+        style_errors.sort_by_key(|e| (e.span.location_offset(), e.kind));
+        style_errors.dedup_by_key(|e| (e.span.location_offset(), e.kind));
 
-        Ok((
-            json,
-            style_errors.into_iter().map(|e| {
-                error::generate_error_message(
-                    &self.style,
-                    &e,
-                ).unwrap()
-            }).collect(),
-        ))
+        Ok((json, style_errors))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
+    use std::sync::LazyLock;
+    use crate::parser::naming::KeyCase;
+    use crate::parser::order::{Grouping, KeyOrder, PinnedKeys};
+    use crate::{SortAlgorithm, SortOrder};
     use super::*;
 
     #[test]
@@ -466,9 +373,10 @@ mod test {
         let span = Span::new("\"hello\": \"world\"");
         let parser = Parser {
             style: JsonStyle::IGNORE,
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
-        let result = parser.parse_entry(span, 0, &mut style_errors);
+        let result = parser.parse_entry(span, 0, "", &mut style_errors);
         assert_eq!(result.is_ok(), true);
         assert_eq!(style_errors.len(), 0);
     }
@@ -483,10 +391,12 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
-        let result = parser.parse_entry(span, 0, &mut style_errors);
+        let result = parser.parse_entry(span, 0, "", &mut style_errors);
         assert_eq!(result.is_ok(), true);
         assert_eq!(style_errors.len(), 1);
     }
@@ -501,10 +411,12 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
-        let result = parser.parse_entry(span, 0, &mut style_errors);
+        let result = parser.parse_entry(span, 0, "", &mut style_errors);
         assert_eq!(result.is_ok(), true);
         assert_eq!(style_errors.len(), 0);
     }
@@ -519,7 +431,9 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -537,7 +451,9 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -555,7 +471,9 @@ mod test {
                 post_colon: Some(""),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -573,7 +491,9 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -591,7 +511,9 @@ mod test {
                 post_colon: Some(""),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -609,7 +531,9 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -629,7 +553,9 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -649,7 +575,9 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -669,7 +597,9 @@ mod test {
                 post_colon: Some(" "),
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NONE,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -689,7 +619,9 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NORMAL,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -709,7 +641,9 @@ mod test {
                 post_colon: None,
                 order: SortOrder::ASC,
                 sort_algorithm: SortAlgorithm::NORMAL,
+                key_order: None,
             },
+            naming: KeyNaming::default(),
         };
         let mut style_errors = Vec::new();
         let result = parser.parse_object(span, 0, &mut style_errors);
@@ -717,6 +651,55 @@ mod test {
         assert_eq!(style_errors.len(), 1);
     }
 
+    #[test]
+    fn sorted_collation() {
+        let parser = |sort_algorithm| Parser {
+            style: JsonStyle::STYLED {
+                line_endings: LineEnding::IGNORE,
+                indentation: None,
+                post_colon: None,
+                order: SortOrder::ASC,
+                sort_algorithm,
+                key_order: None,
+            },
+            naming: KeyNaming::default(),
+        };
+        let span = Span::new("{\"Äpfel\": \"x\", \"apple\": \"x\", \"item2\": \"x\", \"Item10\": \"x\", \"Zebra\": \"x\"}");
+        let errors = |sort_algorithm| {
+            let mut style_errors = Vec::new();
+            parser(sort_algorithm).parse_object(span, 0, &mut style_errors).unwrap();
+            style_errors.len()
+        };
+
+        assert_eq!(errors(SortAlgorithm::NORMAL), 2);
+        assert_eq!(errors(SortAlgorithm::CASE_INSENSITIVE), 2);
+        assert_eq!(errors(SortAlgorithm::NATURAL_CASE_INSENSITIVE), 1);
+        assert_eq!(errors(SortAlgorithm::COLLATION), 1);
+    }
+
+    #[test]
+    fn sorted_key_order() {
+        static KEY_ORDER: LazyLock<KeyOrder> = LazyLock::new(|| KeyOrder {
+            pinned: vec![PinnedKeys::parse("$schema,$id,title,type").unwrap()],
+            grouping: Some(Grouping::StringsFirst),
+        });
+        let parser = Parser::new(JsonStyle::STYLED {
+            line_endings: LineEnding::IGNORE,
+            indentation: None,
+            post_colon: None,
+            order: SortOrder::ASC,
+            sort_algorithm: SortAlgorithm::NORMAL,
+            key_order: Some(&KEY_ORDER),
+        });
+        let errors = |s| parser.parse(s).unwrap().1.iter()
+            .map(|e| e.span.location_line())
+            .collect::<Vec<_>>();
+
+        // "definitions" is an object preceding strings, "title" precedes "type" and "age" precedes "name"
+        assert_eq!(errors(include_str!("../../testdata/json/test4.json")), vec![3, 6, 11, 15, 18]);
+        assert!(errors("{\"$schema\": \"\", \"title\": \"\", \"type\": \"\", \"a\": \"\", \"b\": {}}").is_empty());
+    }
+
     #[test]
     fn object_parsing_indent_post_colon_lf_err() {
         let s = [
@@ -736,7 +719,9 @@ mod test {
                     post_colon: Some(" "),
                     order: SortOrder::ASC,
                     sort_algorithm: SortAlgorithm::NONE,
+                    key_order: None,
                 },
+                naming: KeyNaming::default(),
             };
             let mut style_errors = Vec::new();
             let result = parser.parse_object(span, 0, &mut style_errors);
@@ -744,4 +729,73 @@ mod test {
             assert_eq!(style_errors.len(), 1);
         });
     }
+
+    #[test]
+    fn parse_reports_every_violation() {
+        let parser = Parser::new(JsonStyle::STYLED {
+            line_endings: LineEnding::LF,
+            indentation: Some("    "),
+            post_colon: Some(" "),
+            order: SortOrder::ASC,
+            sort_algorithm: SortAlgorithm::NORMAL,
+            key_order: None,
+        });
+        let result = parser.parse(
+            "{\n    \"b\":  \"x\",\n  \"a\": \"x\" \n}"
+        );
+        assert!(result.is_ok());
+        let (_, style_errors) = result.unwrap();
+        assert_eq!(
+            style_errors.iter().map(|e| e.kind).collect::<Vec<_>>(),
+            vec![
+                StyleErrorKind::PostColonTooMuch,
+                StyleErrorKind::NotEnoughIndentation,
+                StyleErrorKind::Sorting,
+                StyleErrorKind::MaybeSpace,
+            ],
+        );
+        style_errors.iter().for_each(|e| {
+            assert!(!e.kind.message(&parser.style, e.span.location_line(), e.span.get_column()).is_empty());
+        });
+    }
+
+    #[test]
+    fn directives_are_not_checked() {
+        static KEY_ORDER: LazyLock<KeyOrder> = LazyLock::new(|| KeyOrder {
+            pinned: vec![PinnedKeys::parse("a").unwrap()],
+            grouping: None,
+        });
+        let parser = Parser::new(JsonStyle::STYLED {
+            line_endings: LineEnding::IGNORE,
+            indentation: None,
+            post_colon: None,
+            order: SortOrder::ASC,
+            sort_algorithm: SortAlgorithm::NORMAL,
+            key_order: Some(&KEY_ORDER),
+        }).with_naming(KeyNaming {
+            objects: None,
+            leaves: Some(KeyCase::Snake),
+        });
+        let (_, style_errors) = parser
+            .parse("{\"a\": \"\", \"//jsoncpl-disable\": \"missing-key\", \"b\": \"\"}")
+            .unwrap();
+
+        assert!(style_errors.is_empty());
+    }
+
+    #[test]
+    fn key_naming() {
+        let parser = Parser::new(JsonStyle::IGNORE).with_naming(KeyNaming {
+            objects: Some(KeyCase::Camel),
+            leaves: Some(KeyCase::ScreamingSnake),
+        });
+        let (_, style_errors) = parser
+            .parse("{\"introduction\": \"a\", \"shared_actions\": {\"MARK_READ\": \"b\"}}")
+            .unwrap();
+
+        assert_eq!(
+            style_errors.iter().map(|e| (e.kind, e.span.get_column())).collect::<Vec<_>>(),
+            vec![(StyleErrorKind::LeafKeyNaming, 3), (StyleErrorKind::ObjectKeyNaming, 24)],
+        );
+    }
 }
@@ -0,0 +1,376 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::parser::document::{escape, JsonDocument, JsonValue};
+use crate::parser::model::{JsonObject, JsonString, JsonType};
+
+/// The path of a key through nested objects, written as JSON Pointer (`/a/b`) or dotted (`a.b`).
+///
+/// The segments are unescaped keys, dots in dotted paths are escaped as `\.`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyPath {
+    segments: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// There is no key at the path
+    NotFound(KeyPath),
+    /// The path continues below a string value
+    NotAnObject(KeyPath),
+    /// The target key is already taken
+    Exists(KeyPath),
+    /// The target lies inside the moved entry
+    IntoItself(KeyPath),
+}
+
+impl KeyPath {
+    pub fn new(segments: Vec<String>) -> KeyPath {
+        KeyPath {
+            segments,
+        }
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn join(&self, segment: &str) -> KeyPath {
+        let mut segments = self.segments.clone();
+        segments.push(segment.to_string());
+        KeyPath::new(segments)
+    }
+
+    pub fn starts_with(&self, prefix: &KeyPath) -> bool {
+        self.segments.starts_with(&prefix.segments)
+    }
+
+    pub fn parent(&self) -> Option<(KeyPath, &str)> {
+        self.segments.split_last()
+            .map(|(last, parent)| (KeyPath::new(parent.to_vec()), last.as_str()))
+    }
+
+    pub fn parse(path: &str) -> Result<KeyPath, String> {
+        let segments = if let Some(pointer) = path.strip_prefix('/') {
+            pointer.split('/').map(unescape_pointer).collect::<Result<Vec<_>, _>>()?
+        } else {
+            split_dotted(path)?
+        };
+        if segments.iter().all(String::is_empty) && segments.len() <= 1 {
+            return Err("expected at least one key".to_string());
+        }
+
+        Ok(KeyPath::new(segments))
+    }
+
+    /// The path as JSON Pointer, e.g. `/a/b`.
+    pub fn pointer(&self) -> String {
+        self.segments.iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect()
+    }
+
+    /// The path in dotted notation, e.g. `a.b`.
+    pub fn dotted(&self) -> String {
+        self.segments.iter()
+            .map(|segment| segment.replace('.', "\\."))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Whether `key`, a key as written in the source, is the segment at `index`.
+    fn matches(&self, index: usize, key: &str) -> bool {
+        escape(&self.segments[index]) == key
+    }
+}
+
+fn unescape_pointer(segment: &str) -> Result<String, String> {
+    let mut unescaped = String::new();
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('0') => unescaped.push('~'),
+            Some('1') => unescaped.push('/'),
+            _ => return Err(format!("invalid escape sequence in \"{}\", expected ~0 or ~1", segment)),
+        }
+    }
+
+    Ok(unescaped)
+}
+
+fn split_dotted(path: &str) -> Result<Vec<String>, String> {
+    let mut segments = vec![String::new()];
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.clone().next() == Some('.') => {
+                chars.next();
+                segments.last_mut().unwrap().push('.');
+            }
+            '.' => segments.push(String::new()),
+            _ => segments.last_mut().unwrap().push(c),
+        }
+    }
+    if segments.len() > 1 && segments.iter().any(String::is_empty) {
+        return Err(format!("empty key in \"{}\"", path));
+    }
+
+    Ok(segments)
+}
+
+impl FromStr for KeyPath {
+    type Err = String;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        KeyPath::parse(path)
+    }
+}
+
+impl<'a> From<&[JsonString<'a>]> for KeyPath {
+    fn from(path: &[JsonString<'a>]) -> Self {
+        KeyPath::new(path.iter().map(|key| key.value.to_string()).collect())
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self.segments.iter()
+            .map(|segment| format!("\"{}\"", segment))
+            .collect::<Vec<_>>();
+        write!(f, "{}", keys.join("->"))
+    }
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::NotFound(path) => write!(f, "Can not find key `{}`", path),
+            EditError::NotAnObject(path) => write!(f, "The value of key `{}` is not an object", path),
+            EditError::Exists(path) => write!(f, "The key `{}` already exists", path),
+            EditError::IntoItself(path) => write!(f, "Can not move key `{}` into itself", path),
+        }
+    }
+}
+
+impl<'a> JsonObject<'a> {
+    /// Finds the value at `path`, the root can not be looked up. A parsed object borrows the content of
+    /// its file and can not be edited, edits are applied to the [`JsonDocument`] converted from it.
+    pub fn get(&self, path: &KeyPath) -> Option<&JsonType<'a>> {
+        let (last, parents) = path.segments.split_last()?;
+        let mut object = self;
+        for (index, _) in parents.iter().enumerate() {
+            match object.values.iter().find(|(key, _)| path.matches(index, key.value)) {
+                Some((_, JsonType::Object(child))) => object = child,
+                _ => return None,
+            }
+        }
+
+        object.values.iter()
+            .find(|(key, _)| escape(last) == key.value)
+            .map(|(_, value)| value)
+    }
+}
+
+impl JsonDocument {
+    /// Finds the value at `path`, the root can not be looked up.
+    pub fn get(&self, path: &KeyPath) -> Option<&JsonValue> {
+        let (last, parents) = path.segments.split_last()?;
+        let mut object = self;
+        for (index, _) in parents.iter().enumerate() {
+            match object.values.iter().find(|(key, _)| path.matches(index, key)) {
+                Some((_, JsonValue::Object(child))) => object = child,
+                _ => return None,
+            }
+        }
+
+        object.values.iter()
+            .find(|(key, _)| escape(last) == *key)
+            .map(|(_, value)| value)
+    }
+
+    /// Finds the object at `path`, missing objects are appended if `create` is set.
+    fn object_mut(&mut self, path: &KeyPath, create: bool) -> Result<&mut JsonDocument, EditError> {
+        let mut object = self;
+        for index in 0..path.segments.len() {
+            let position = match object.values.iter().position(|(key, _)| path.matches(index, key)) {
+                Some(position) => position,
+                None if create => {
+                    object.values.push((escape(&path.segments[index]), JsonValue::Object(JsonDocument::new())));
+                    object.values.len() - 1
+                }
+                None => return Err(EditError::NotFound(KeyPath::new(path.segments[..=index].to_vec()))),
+            };
+            object = match &mut object.values[position].1 {
+                JsonValue::Object(child) => child,
+                JsonValue::String(_) =>
+                    return Err(EditError::NotAnObject(KeyPath::new(path.segments[..=index].to_vec()))),
+            };
+        }
+
+        Ok(object)
+    }
+
+    /// Sets the value at `path` and returns the replaced value, missing parent objects are created.
+    pub fn set(&mut self, path: &KeyPath, value: JsonValue) -> Result<Option<JsonValue>, EditError> {
+        let (parent, last) = path.parent().ok_or_else(|| EditError::NotFound(path.clone()))?;
+        let object = self.object_mut(&parent, true)?;
+        let key = escape(last);
+        match object.values.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, old)) => Ok(Some(std::mem::replace(old, value))),
+            None => {
+                object.values.push((key, value));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes the entry at `path` and returns its value.
+    pub fn remove(&mut self, path: &KeyPath) -> Result<JsonValue, EditError> {
+        let (parent, last) = path.parent().ok_or_else(|| EditError::NotFound(path.clone()))?;
+        let object = self.object_mut(&parent, false)?;
+        let key = escape(last);
+        match object.values.iter().position(|(k, _)| *k == key) {
+            Some(position) => Ok(object.values.remove(position).1),
+            None => Err(EditError::NotFound(path.clone())),
+        }
+    }
+
+    /// Renames the key at `path` to `name`, the entry keeps its position.
+    pub fn rename(&mut self, path: &KeyPath, name: &str) -> Result<(), EditError> {
+        let (parent, last) = path.parent().ok_or_else(|| EditError::NotFound(path.clone()))?;
+        let object = self.object_mut(&parent, false)?;
+        let (key, new_key) = (escape(last), escape(name));
+        if key != new_key && object.values.iter().any(|(k, _)| *k == new_key) {
+            return Err(EditError::Exists(parent.join(name)));
+        }
+        match object.values.iter_mut().find(|(k, _)| *k == key) {
+            Some((k, _)) => {
+                *k = new_key;
+                Ok(())
+            }
+            None => Err(EditError::NotFound(path.clone())),
+        }
+    }
+
+    /// Moves the entry at `from` to `to`, missing parent objects are created. An existing entry at
+    /// `to` is only replaced if `force` is set, the document is left unchanged on errors.
+    pub fn move_to(&mut self, from: &KeyPath, to: &KeyPath, force: bool) -> Result<(), EditError> {
+        if self.get(from).is_none() {
+            return Err(EditError::NotFound(from.clone()));
+        }
+        if from == to {
+            return Ok(());
+        }
+        if to.starts_with(from) {
+            return Err(EditError::IntoItself(from.clone()));
+        }
+        if !force && self.get(to).is_some() {
+            return Err(EditError::Exists(to.clone()));
+        }
+
+        let mut moved = self.clone();
+        let value = moved.remove(from)?;
+        moved.set(to, value)?;
+        *self = moved;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parser::model::JsonStyle;
+    use crate::parser::parser::Parser;
+    use super::*;
+
+    fn path(path: &str) -> KeyPath {
+        KeyPath::parse(path).unwrap()
+    }
+
+    fn document() -> JsonDocument {
+        JsonDocument::new()
+            .with("a", JsonDocument::new().with("b", "1").with("c", "2"))
+            .with("d", "3")
+    }
+
+    #[test]
+    fn parse_notations() {
+        let expected = KeyPath::new(vec!["a/b".to_string(), "c.d".to_string(), "~".to_string()]);
+        assert_eq!(path("/a~1b/c.d/~0"), expected);
+        assert_eq!(path("a/b.c\\.d.~"), expected);
+        assert_eq!(expected.pointer(), "/a~1b/c.d/~0");
+        assert_eq!(expected.dotted(), "a/b.c\\.d.~");
+        assert_eq!(expected.to_string(), "\"a/b\"->\"c.d\"->\"~\"");
+
+        assert!(KeyPath::parse("").is_err());
+        assert!(KeyPath::parse("/").is_err());
+        assert!(KeyPath::parse("a..b").is_err());
+        assert!(KeyPath::parse("/a~2").is_err());
+    }
+
+    #[test]
+    fn get_parsed() {
+        let (json, _) = Parser::new(JsonStyle::IGNORE)
+            .parse("{\"a\": {\"b\": \"1\"}, \"c\": \"2\"}")
+            .unwrap();
+
+        assert!(matches!(json.get(&path("a.b")), Some(JsonType::String(s)) if s.value == "1"));
+        assert!(matches!(json.get(&path("/a")), Some(JsonType::Object(_))));
+        assert!(json.get(&path("c.b")).is_none());
+        assert!(json.get(&path("x")).is_none());
+    }
+
+    #[test]
+    fn set_creates_objects() {
+        let mut document = document();
+        assert_eq!(document.set(&path("a.b"), "4".into()), Ok(Some("1".into())));
+        assert_eq!(document.set(&path("e.f"), "5".into()), Ok(None));
+        assert_eq!(document.get(&path("e.f")), Some(&"5".into()));
+        assert_eq!(document.set(&path("d.g"), "6".into()), Err(EditError::NotAnObject(path("d"))));
+    }
+
+    #[test]
+    fn remove_and_rename() {
+        let mut document = document();
+        assert_eq!(document.remove(&path("a.b")), Ok("1".into()));
+        assert_eq!(document.remove(&path("a.b")), Err(EditError::NotFound(path("a.b"))));
+        assert_eq!(document.remove(&path("x.b")), Err(EditError::NotFound(path("x"))));
+
+        assert_eq!(document.rename(&path("a"), "d"), Err(EditError::Exists(path("d"))));
+        assert_eq!(document.rename(&path("a.c"), "e"), Ok(()));
+        assert_eq!(document, JsonDocument::new()
+            .with("a", JsonDocument::new().with("e", "2"))
+            .with("d", "3"));
+    }
+
+    #[test]
+    fn move_subtree() {
+        let mut document = document();
+        assert_eq!(document.move_to(&path("a"), &path("e.f"), false), Ok(()));
+        assert_eq!(document, JsonDocument::new()
+            .with("d", "3")
+            .with("e", JsonDocument::new()
+                .with("f", JsonDocument::new().with("b", "1").with("c", "2"))));
+    }
+
+    #[test]
+    fn move_refuses_collisions() {
+        let mut document = document();
+        assert_eq!(document.move_to(&path("a.b"), &path("d"), false), Err(EditError::Exists(path("d"))));
+        assert_eq!(document.move_to(&path("a"), &path("a.x"), false), Err(EditError::IntoItself(path("a"))));
+        assert_eq!(document.move_to(&path("a.b"), &path("d.x"), false), Err(EditError::NotAnObject(path("d"))));
+        assert_eq!(document, self::document());
+
+        assert_eq!(document.move_to(&path("a.b"), &path("d"), true), Ok(()));
+        assert_eq!(document, JsonDocument::new()
+            .with("a", JsonDocument::new().with("c", "2"))
+            .with("d", "1"));
+    }
+}
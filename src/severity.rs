@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use crate::diagnostic::{self, Diagnostic};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The diagnostics are dropped
+    Off,
+    /// The diagnostics are reported without failing the run
+    Warn,
+    Error,
+}
+
+/// The severity of the rule with the ID, e.g. `sorting=warn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleSeverity {
+    pub rule: &'static str,
+    pub severity: Severity,
+}
+
+/// The severity of every rule, rules which are not configured are errors.
+#[derive(Debug, Clone, Default)]
+pub struct Severities {
+    rules: BTreeMap<&'static str, Severity>,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Severity::Off),
+            "warn" => Ok(Severity::Warn),
+            "error" => Ok(Severity::Error),
+            _ => Err(format!("expected off, warn or error but found \"{}\"", s)),
+        }
+    }
+}
+
+impl RuleSeverity {
+    /// Parses a severity written as `<rule>=<severity>`, e.g. `untranslated=warn`.
+    pub fn parse(rule: &str) -> Result<RuleSeverity, String> {
+        let (id, severity) = rule.split_once('=')
+            .ok_or_else(|| format!("expected <rule>=<severity> but found \"{}\"", rule))?;
+        Ok(RuleSeverity {
+            rule: diagnostic::rule(id)?,
+            severity: severity.parse()?,
+        })
+    }
+}
+
+impl Severities {
+    pub fn new(rules: Vec<RuleSeverity>) -> Severities {
+        Severities {
+            rules: rules.into_iter().map(|rule| (rule.rule, rule.severity)).collect(),
+        }
+    }
+
+    pub fn of(&self, diagnostic: &Diagnostic) -> Severity {
+        self.of_rule(diagnostic.rule())
+    }
+
+    /// The severity of the rule with the ID.
+    pub fn of_rule(&self, rule: &str) -> Severity {
+        self.rules.get(rule).copied().unwrap_or(Severity::Error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+    use super::*;
+
+    #[test]
+    fn configured_severities() {
+        let severities = Severities::new(vec![
+            RuleSeverity::parse("missing-key=warn").unwrap(),
+            RuleSeverity::parse("missing-key=off").unwrap(),
+        ]);
+        let missing = Diagnostic::MissingKey {
+            file: PathBuf::from("de.json"),
+            key: vec!["a".to_string()],
+        };
+        let parse = Diagnostic::Parse {
+            file: PathBuf::from("de.json"),
+            message: String::new(),
+        };
+
+        assert_eq!(severities.of(&missing), Severity::Off);
+        assert_eq!(severities.of(&parse), Severity::Error);
+        assert!(RuleSeverity::parse("sorting").is_err());
+        assert!(RuleSeverity::parse("sort=warn").is_err());
+        assert!(RuleSeverity::parse("sorting=info").is_err());
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+use std::path::PathBuf;
+use serde::Serialize;
+use crate::checks::entry_parity::{join, Sources};
+use crate::checks::file_parity::file_parity;
+use crate::checks::suppress::DIRECTIVE;
+use crate::diagnostic::Diagnostic;
+use crate::io::{File, LoadedFile};
+use crate::parser::document::unescape;
+use crate::parser::model::{JsonObject, JsonType, JsonStyle};
+use crate::parser::parser::Parser;
+
+/// The numbers of keys of a file or a folder, only keys of strings are counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Counts {
+    /// The keys of the file in any folder
+    pub total: usize,
+    /// The keys missing in this file
+    pub missing: usize,
+    /// The values identical to the value in the first folder
+    pub untranslated: usize,
+    /// The empty and whitespace only values
+    pub empty: usize,
+    /// The keys missing in the first folder
+    pub obsolete: usize,
+}
+
+impl Counts {
+    /// The keys which are neither missing, untranslated nor empty.
+    pub fn translated(&self) -> usize {
+        self.total - self.missing - self.untranslated - self.empty
+    }
+
+    /// The percentage of translated keys, an empty file is complete.
+    pub fn complete(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated() as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+impl AddAssign for Counts {
+    fn add_assign(&mut self, other: Counts) {
+        self.total += other.total;
+        self.missing += other.missing;
+        self.untranslated += other.untranslated;
+        self.empty += other.empty;
+        self.obsolete += other.obsolete;
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStats {
+    /// The path of the file relative to the folder
+    pub file: PathBuf,
+    #[serde(flatten)]
+    pub counts: Counts,
+    pub complete: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocaleStats {
+    pub folder: PathBuf,
+    #[serde(flatten)]
+    pub counts: Counts,
+    pub complete: f64,
+    pub files: Vec<FileStats>,
+}
+
+/// Collects the value of every string, directives are no translations.
+fn leaves<'a>(object: &JsonObject<'a>, path: &[&'a str], found: &mut BTreeMap<Vec<&'a str>, &'a str>) {
+    for (key, value) in object.values.iter().filter(|(key, _)| key.value != DIRECTIVE) {
+        let mut path = path.to_vec();
+        path.push(key.value);
+        match value {
+            JsonType::Object(child) => leaves(child, &path, found),
+            JsonType::String(string) => {
+                found.insert(path, string.value);
+            }
+        }
+    }
+}
+
+/// Counts the keys of the files of a group, `files` holds the file of every folder if it exists.
+fn count_group(files: &[Option<(&LoadedFile, JsonObject)>]) -> Vec<Counts> {
+    let mut objects = files.iter().flatten().map(|(_, object)| object);
+    let mut keys = BTreeMap::new();
+    if let Some(first) = objects.next() {
+        let mut union = first.clone();
+        let mut sources = Sources::new();
+        for (index, object) in objects.enumerate() {
+            join(&mut Vec::new(), &mut union, &mut sources, object, index + 1);
+        }
+        leaves(&union, &[], &mut keys);
+    }
+    let values = files.iter()
+        .map(|file| {
+            let mut found = BTreeMap::new();
+            if let Some((_, object)) = file {
+                leaves(object, &[], &mut found);
+            }
+            found
+        })
+        .collect::<Vec<_>>();
+
+    values.iter().enumerate()
+        .map(|(index, found)| {
+            let reference = &values[0];
+            let mut counts = Counts {
+                total: keys.len(),
+                ..Counts::default()
+            };
+            for key in keys.keys() {
+                match found.get(key) {
+                    None => counts.missing += 1,
+                    Some(value) if unescape(value).trim().is_empty() => counts.empty += 1,
+                    Some(value) if index > 0 && reference.get(key) == Some(value) => counts.untranslated += 1,
+                    Some(_) => {}
+                }
+                if found.contains_key(key) && !reference.contains_key(key) {
+                    counts.obsolete += 1;
+                }
+            }
+
+            counts
+        })
+        .collect()
+}
+
+/// Counts the keys of every file of every folder, the first folder is the reference for untranslated and
+/// obsolete keys. Files which can not be parsed are counted as missing and reported.
+pub fn stats(folders: Vec<(PathBuf, Vec<File>)>) -> (Vec<LocaleStats>, Vec<Diagnostic>) {
+    let roots = folders.iter().map(|(root, _)| root.clone()).collect::<Vec<_>>();
+    let mut locales = roots.iter()
+        .map(|root| LocaleStats {
+            folder: root.clone(),
+            counts: Counts::default(),
+            complete: 0.0,
+            files: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    let mut errors = Vec::new();
+    for (path, files) in file_parity(folders, &mut Vec::new()) {
+        let loaded = files.into_iter().map(File::load).collect::<Vec<_>>();
+        let group = roots.iter()
+            .map(|root| {
+                let file = loaded.iter().find(|file| file.path().strip_prefix(root).is_ok_and(|p| p == path))?;
+                match Parser::new(JsonStyle::IGNORE).parse(file.content()) {
+                    Ok((json, _)) => Some((file, json)),
+                    Err(e) => {
+                        errors.push(Diagnostic::Parse {
+                            file: file.path().clone(),
+                            message: format!("{}", e),
+                        });
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        for (locale, counts) in locales.iter_mut().zip(count_group(&group)) {
+            locale.counts += counts;
+            locale.files.push(FileStats {
+                file: path.clone(),
+                counts,
+                complete: counts.complete(),
+            });
+        }
+    }
+    for locale in &mut locales {
+        locale.complete = locale.counts.complete();
+    }
+
+    (locales, errors)
+}
+
+const COLUMNS: [&str; 8] = ["folder", "file", "total", "missing", "untranslated", "empty", "obsolete", "complete"];
+
+fn rows(locales: &[LocaleStats]) -> Vec<[String; 8]> {
+    let row = |folder: &PathBuf, file: &str, counts: &Counts| [
+        folder.to_str().unwrap().to_string(),
+        file.to_string(),
+        counts.total.to_string(),
+        counts.missing.to_string(),
+        counts.untranslated.to_string(),
+        counts.empty.to_string(),
+        counts.obsolete.to_string(),
+        format!("{:.1}", counts.complete()),
+    ];
+
+    locales.iter()
+        .flat_map(|locale| {
+            std::iter::once(row(&locale.folder, "", &locale.counts))
+                .chain(locale.files.iter().map(|file| row(&locale.folder, file.file.to_str().unwrap(), &file.counts)))
+        })
+        .collect()
+}
+
+/// Renders the statistics as a table with a row for every folder followed by rows for its files.
+pub fn table(locales: &[LocaleStats]) -> String {
+    let rows = rows(locales);
+    let widths = (0..COLUMNS.len())
+        .map(|column| rows.iter().map(|row| row[column].len()).chain([COLUMNS[column].len()]).max().unwrap())
+        .collect::<Vec<_>>();
+    std::iter::once(COLUMNS.map(str::to_string))
+        .chain(rows)
+        .map(|row| {
+            row.iter().zip(&widths).enumerate()
+                .map(|(column, (cell, width))| match column {
+                    0 | 1 => format!("{:<width$}", cell, width = width),
+                    _ => format!("{:>width$}", cell, width = width),
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the statistics as CSV, the rows of the folders have an empty file.
+pub fn csv(locales: &[LocaleStats]) -> String {
+    let quote = |cell: &String| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+
+    std::iter::once(COLUMNS.map(str::to_string))
+        .chain(rows(locales))
+        .map(|row| row.iter().map(quote).collect::<Vec<_>>().join(","))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::fixture;
+    use crate::io::read_folders;
+    use super::*;
+
+    fn testdata() -> Vec<LocaleStats> {
+        let folders = ["en", "de", "fr"].map(|locale| PathBuf::from("testdata/i18n").join(locale));
+        let (locales, errors) = stats(read_folders(&folders));
+        assert!(errors.is_empty());
+        locales
+    }
+
+    #[test]
+    fn count_testdata() {
+        let locales = testdata();
+        let counts = locales.iter().map(|locale| locale.counts).collect::<Vec<_>>();
+
+        // fr lacks a key of common.json and main/editor.json, de uses the same editor title as en
+        assert_eq!(counts, vec![
+            Counts { total: 4, ..Counts::default() },
+            Counts { total: 4, untranslated: 1, ..Counts::default() },
+            Counts { total: 4, missing: 2, ..Counts::default() },
+        ]);
+        assert_eq!(locales[2].complete, 50.0);
+        assert_eq!(locales[2].files[1].file, PathBuf::from("main/editor.json"));
+    }
+
+    #[test]
+    fn count_obsolete_and_empty() {
+        let files = fixture::files(&[("en.json", "{\"a\": \"A\"}"), ("de.json", "{\"a\": \" \", \"b\": \"B\"}")]);
+        let group = fixture::parsed(&files).into_iter().map(Some).collect::<Vec<_>>();
+
+        assert_eq!(count_group(&group), vec![
+            Counts { total: 2, missing: 1, ..Counts::default() },
+            Counts { total: 2, empty: 1, obsolete: 1, ..Counts::default() },
+        ]);
+    }
+
+    #[test]
+    fn directives_are_not_counted() {
+        let files = fixture::files(&[
+            ("en.json", "{\"//jsoncpl-disable\": \"sorting\", \"a\": \"A\", \"b\": {\"//jsoncpl-disable\": \"length\"}}"),
+            ("de.json", "{\"a\": \"B\", \"b\": {}}"),
+        ]);
+        let group = fixture::parsed(&files).into_iter().map(Some).collect::<Vec<_>>();
+
+        assert_eq!(count_group(&group), vec![
+            Counts { total: 1, ..Counts::default() },
+            Counts { total: 1, ..Counts::default() },
+        ]);
+    }
+
+    #[test]
+    fn render_csv() {
+        let csv = csv(&testdata());
+
+        assert_eq!(csv.lines().next().unwrap(), "folder,file,total,missing,untranslated,empty,obsolete,complete");
+        assert!(csv.lines().any(|line| line == "testdata/i18n/fr,main/editor.json,1,1,0,0,0,0.0"));
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use regex::Regex;
+
+/// The patterns used if none are configured: `translate('...')`, `t("...")` and `'...' | translate`.
+pub const DEFAULT_PATTERNS: [&str; 3] = [
+    r#"\btranslate\(\s*['"`]([^'"`$\s]+)"#,
+    r#"\bt\(\s*['"`]([^'"`$\s]+)"#,
+    r#"['"`]([^'"`$\s]+)['"`]\s*\|\s*translate\b"#,
+];
+
+/// Where a key is used in the source code, the column is the byte offset of the key in the line, starting at 1.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Usage {
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: usize,
+}
+
+/// The translation keys used in the source code, by their dotted path.
+#[derive(Debug, Default)]
+pub struct Usages {
+    /// Keys used literally, e.g. `t('shared.SAVE')`
+    pub keys: BTreeMap<String, Vec<Usage>>,
+    /// Prefixes of keys built at runtime, e.g. `t('errors.' + code)` or `` t(`errors.${code}`) ``
+    pub prefixes: BTreeMap<String, Vec<Usage>>,
+}
+
+impl Usages {
+    /// Whether the key at the dotted `path`, or one of its parents, is used.
+    /// With `dynamic` set, keys starting with a dynamic prefix count as used as well.
+    pub fn uses(&self, path: &str, dynamic: bool) -> bool {
+        let mut parent = path;
+        loop {
+            if self.keys.contains_key(parent) {
+                return true;
+            }
+            match parent.rfind('.') {
+                Some(index) => parent = &parent[..index],
+                None => break,
+            }
+        }
+
+        dynamic && self.prefixes.keys().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+}
+
+/// Compiles `patterns`, or the default patterns if there are none. The first capture group of a
+/// pattern, or the group named `key`, is the used key.
+pub fn patterns(patterns: &[String]) -> Result<Vec<Regex>, regex::Error> {
+    if patterns.is_empty() {
+        DEFAULT_PATTERNS.iter().map(|pattern| Regex::new(pattern)).collect()
+    } else {
+        patterns.iter().map(|pattern| Regex::new(pattern)).collect()
+    }
+}
+
+/// Whether the key captured before `rest` is only the beginning of a key built at runtime.
+fn is_dynamic(key: &str, rest: &str) -> bool {
+    let closed = rest.trim_start_matches(['\'', '"', '`']).trim_start();
+    key.ends_with('.') || rest.starts_with("${") || (closed.len() < rest.len() && closed.starts_with('+'))
+}
+
+/// Collects the key usages of a single source text.
+pub fn scan(file: PathBuf, content: &str, patterns: &[Regex], usages: &mut Usages) {
+    for (index, line) in content.lines().enumerate() {
+        for pattern in patterns {
+            for captures in pattern.captures_iter(line) {
+                let Some(key) = captures.name("key").or_else(|| captures.get(1)) else {
+                    continue;
+                };
+                let usage = Usage {
+                    file: file.clone(),
+                    line: index as u32 + 1,
+                    column: key.start() + 1,
+                };
+                if is_dynamic(key.as_str(), &line[key.end()..]) {
+                    usages.prefixes.entry(key.as_str().to_string()).or_default().push(usage);
+                } else {
+                    usages.keys.entry(key.as_str().to_string()).or_default().push(usage);
+                }
+            }
+        }
+    }
+}
+
+/// Collects the key usages of every file matching one of the `sources` globs.
+pub fn scan_sources(sources: &[String], patterns: &[Regex]) -> Result<Usages, String> {
+    let mut usages = Usages::default();
+    for source in sources {
+        let paths = glob::glob(source).map_err(|e| format!("Invalid glob \"{}\": {}", source, e))?;
+        for path in paths {
+            let path = path.map_err(|e| e.to_string())?;
+            if !path.is_file() {
+                continue;
+            }
+            let content = fs::read(&path).map_err(|e| format!("Can not read {}: {}", path.display(), e))?;
+            scan(path, &String::from_utf8_lossy(&content), patterns, &mut usages);
+        }
+    }
+
+    Ok(usages)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn scan_default(content: &str) -> Usages {
+        let mut usages = Usages::default();
+        scan(PathBuf::from("app.ts"), content, &patterns(&[]).unwrap(), &mut usages);
+        usages
+    }
+
+    #[test]
+    fn scan_default_patterns() {
+        let usages = scan_default(concat!(
+            "this.translate('shared.SAVE');\n",
+            "<p>{{ \"main.TITLE\" | translate }}</p> t(`errors.${code}`)\n",
+            "const label = t(\"shared.CANCEL\") + t('errors.' + code);\n",
+        ));
+
+        assert_eq!(usages.keys.keys().collect::<Vec<_>>(), vec!["main.TITLE", "shared.CANCEL", "shared.SAVE"]);
+        assert_eq!(usages.keys["main.TITLE"], vec![Usage { file: PathBuf::from("app.ts"), line: 2, column: 8 }]);
+        assert_eq!(usages.prefixes.keys().collect::<Vec<_>>(), vec!["errors."]);
+        assert_eq!(usages.prefixes["errors."].len(), 2);
+    }
+
+    #[test]
+    fn uses_parents_and_prefixes() {
+        let usages = scan_default("t('shared'); t('errors.' + code);");
+
+        assert!(usages.uses("shared.SAVE", false));
+        assert!(!usages.uses("sharedX", false));
+        assert!(!usages.uses("errors.NOT_FOUND", false));
+        assert!(usages.uses("errors.NOT_FOUND", true));
+    }
+
+    #[test]
+    fn custom_patterns() {
+        let patterns = patterns(&[r#"tr!\("(?P<key>[^"]+)"\)"#.to_string()]).unwrap();
+        let mut usages = Usages::default();
+        scan(PathBuf::from("main.rs"), "tr!(\"a.b\"); t('c')", &patterns, &mut usages);
+
+        assert_eq!(usages.keys.keys().collect::<Vec<_>>(), vec!["a.b"]);
+    }
+}
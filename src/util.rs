@@ -5,12 +5,12 @@ pub fn print_lines(display_lines: Range<u32>, content: &str) -> String {
     let mut buffer = String::new();
     for (i, line_content) in content.lines().enumerate() {
         let i = i + 1;
-        if display_lines.start - 2 <= i as u32 && i as u32 <= display_lines.end + 2 {
+        if display_lines.start.saturating_sub(2).max(1) <= i as u32 && i as u32 <= display_lines.end + 2 {
             buffer = format!(
                 "{} [{}]{}\n",
                 buffer,
                 format!("{}", i).blue(),
-                if display_lines.start - 1 < (i as u32) && (i as u32) < display_lines.end + 1 {
+                if display_lines.start.saturating_sub(1) < (i as u32) && (i as u32) < display_lines.end + 1 {
                     line_content.red()
                 } else {
                     line_content.normal()
@@ -22,3 +22,16 @@ pub fn print_lines(display_lines: Range<u32>, content: &str) -> String {
     buffer.pop();
     buffer
 }
+
+/// A stable FNV-1a hash over `parts`, unlike `DefaultHasher` it does not change between builds.
+pub fn hash(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0xff)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash
+}
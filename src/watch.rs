@@ -0,0 +1,103 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
+use notify::{EventKind, RecursiveMode, Watcher};
+use jsoncpl::io::read_folders;
+
+/// Time to wait for further events after a change, editors tend to write a file in multiple steps.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// The paths of the files of every folder relative to their folder.
+fn relative_files(roots: &[PathBuf]) -> BTreeSet<PathBuf> {
+    read_folders(roots).into_iter()
+        .flat_map(|(root, files)| {
+            files.into_iter()
+                .filter_map(move |file| file.path().strip_prefix(&root).ok().map(Path::to_path_buf))
+        })
+        .collect()
+}
+
+/// The groups to check again for the `changed` paths relative to their folder. `known` are the files
+/// of the last check, deleted files are kept so their group is checked again. Paths which neither exist
+/// in any of the folders nor were known (e.g. temporary files of editors) are skipped.
+fn changed_groups(roots: &[PathBuf], known: &mut BTreeSet<PathBuf>, changed: BTreeSet<PathBuf>) -> Vec<PathBuf> {
+    changed.into_iter()
+        .filter(|relative| {
+            if roots.iter().any(|root| root.join(relative).is_file()) {
+                known.insert(relative.clone());
+                true
+            } else {
+                known.remove(relative)
+            }
+        })
+        .collect()
+}
+
+/// Watches `folders` and calls `run` once per batch of changes with the paths relative to their folder of
+/// the changed groups.
+pub fn watch(folders: &[PathBuf], mut run: impl FnMut(&[PathBuf])) -> notify::Result<()> {
+    let roots = folders.iter()
+        .map(|folder| folder.canonicalize().map_err(notify::Error::io))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut known = relative_files(&roots);
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for root in &roots {
+        watcher.watch(root, RecursiveMode::Recursive)?;
+    }
+
+    while let Ok(event) = rx.recv() {
+        let mut changed = BTreeSet::new();
+        let mut event = Some(event);
+        while let Some(next) = event {
+            let next = next?;
+            if !matches!(next.kind, EventKind::Access(_)) {
+                for path in next.paths {
+                    if let Some(relative) = roots.iter().find_map(|root| path.strip_prefix(root).ok()) {
+                        changed.insert(relative.to_path_buf());
+                    }
+                }
+            }
+            event = rx.recv_timeout(DEBOUNCE).ok();
+        }
+
+        let groups = changed_groups(&roots, &mut known, changed);
+        if !groups.is_empty() {
+            run(&groups);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use super::*;
+
+    #[test]
+    fn groups_of_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let roots = ["de", "en"].map(|folder| dir.path().join(folder));
+        for root in &roots {
+            fs::create_dir(root).unwrap();
+            fs::write(root.join("common.json"), "{}").unwrap();
+            fs::write(root.join("main.json"), "{}").unwrap();
+        }
+        let mut known = relative_files(&roots);
+        let changed = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<BTreeSet<_>>();
+
+        // A temporary file of an editor which is gone again
+        fs::remove_file(roots[0].join("main.json")).unwrap();
+        assert_eq!(
+            changed_groups(&roots, &mut known, changed(&["common.json", "main.json", ".common.json.swp"])),
+            vec![PathBuf::from("common.json"), PathBuf::from("main.json")],
+        );
+
+        // A group deleted in every folder is checked once more
+        fs::remove_file(roots[1].join("main.json")).unwrap();
+        assert_eq!(changed_groups(&roots, &mut known, changed(&["main.json"])), vec![PathBuf::from("main.json")]);
+        assert!(changed_groups(&roots, &mut known, changed(&["main.json"])).is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use std::path::PathBuf;
+use jsoncpl::checks::entry_parity::entry_parity;
+use jsoncpl::checks::file_style::file_style;
+use jsoncpl::checks::suppress::Suppressions;
+use jsoncpl::io::{read_folders, LoadedFile};
+use jsoncpl::lint::{fix, format_file, lint, LintOptions};
+use jsoncpl::parser::naming::{KeyCase, KeyNaming};
+use jsoncpl::severity::{RuleSeverity, Severities, Severity};
+use jsoncpl::{
+    Diagnostic, Generator, JsonStyle, LineEnding, Parser, SortAlgorithm, SortOrder, StyleErrorKind,
+};
+
+const STYLE: JsonStyle = JsonStyle::STYLED {
+    line_endings: LineEnding::LF,
+    indentation: Some("    "),
+    post_colon: Some(" "),
+    sort_algorithm: SortAlgorithm::NATURAL,
+    key_order: None,
+    order: SortOrder::ASC,
+};
+
+#[test]
+fn parser_reports_style_errors() {
+    let (json, errors) = Parser::new(STYLE)
+        .parse("{\n    \"b\": \"1\",\n    \"a\":\"2\"\n}\n")
+        .unwrap();
+
+    assert_eq!(json.values.len(), 2);
+    let kinds = errors.iter().map(|error| error.kind).collect::<Vec<_>>();
+    assert_eq!(kinds, vec![StyleErrorKind::Sorting, StyleErrorKind::PostColon]);
+    assert_eq!(errors[0].span.location_line(), 3);
+}
+
+#[test]
+fn parser_rejects_invalid_json() {
+    assert!(Parser::new(JsonStyle::IGNORE).parse("{\"a\": }").is_err());
+}
+
+#[test]
+fn generator_writes_the_style() {
+    let (json, _) = Parser::new(JsonStyle::IGNORE)
+        .parse("{\"b\":{\"d\":\"1\",\"c\":\"2\"},\"a\":\"3\"}")
+        .unwrap();
+
+    assert_eq!(
+        Generator::new(STYLE).generate(json),
+        "{\n    \"a\": \"3\",\n    \"b\": {\n        \"c\": \"2\",\n        \"d\": \"1\"\n    }\n}",
+    );
+}
+
+#[test]
+fn format_file_reports_parse_errors() {
+    let path = PathBuf::from("en.json");
+    assert_eq!(format_file(&STYLE, &path, "{\"a\":\"b\"}").unwrap(), "{\n    \"a\": \"b\"\n}");
+    assert!(matches!(
+        format_file(&STYLE, &path, "{"),
+        Err(Diagnostic::Parse { file, .. }) if file == path
+    ));
+}
+
+#[test]
+fn checks_produce_diagnostics() {
+    let en = LoadedFile::new(PathBuf::from("en.json"), "{\n    \"a\": \"1\",\n    \"b\": \"2\"\n}\n".to_string());
+    let de = LoadedFile::new(PathBuf::from("de.json"), "{\n    \"a\": \"1\"\n}\n".to_string());
+
+    let mut errors = Vec::new();
+    let jsons = [&en, &de].into_iter()
+        .map(|file| (file, file_style(&Parser::new(STYLE), file, &Suppressions::default(), &mut errors).unwrap()))
+        .collect::<Vec<_>>();
+    assert!(errors.is_empty());
+
+    entry_parity(&jsons, &Suppressions::default(), &mut errors);
+    assert_eq!(errors, vec![Diagnostic::MissingKey {
+        file: PathBuf::from("de.json"),
+        key: vec!["b".to_string()],
+    }]);
+}
+
+#[test]
+fn lint_folders() {
+    let folders = ["de", "en", "fr"].map(|locale| PathBuf::from("testdata/i18n").join(locale));
+    let report = lint(&STYLE, read_folders(&folders), &LintOptions::default());
+
+    assert_eq!(report.diagnostics().cloned().collect::<Vec<_>>(), vec![
+        Diagnostic::NotFound {
+            file: PathBuf::from("main/editor.json"),
+            folder: folders[2].clone(),
+        },
+        Diagnostic::MissingKey {
+            file: folders[2].join("common.json"),
+            key: vec!["shared".to_string(), "HIDE_INTRO".to_string()],
+        },
+    ]);
+    assert_eq!(
+        report.groups.iter().map(|group| group.path.clone()).collect::<Vec<_>>(),
+        vec![PathBuf::from("common.json"), PathBuf::from("main/editor.json")],
+    );
+}
+
+#[test]
+fn lint_with_severities() {
+    let folders = ["de", "en", "fr"].map(|locale| PathBuf::from("testdata/i18n").join(locale));
+    let options = LintOptions {
+        severities: Severities::new(vec![
+            RuleSeverity::parse("missing-file=off").unwrap(),
+            RuleSeverity::parse("missing-key=warn").unwrap(),
+        ]),
+        ..LintOptions::default()
+    };
+    let report = lint(&STYLE, read_folders(&folders), &options);
+
+    assert_eq!(report.diagnostics().count(), 1);
+    assert_eq!(report.count(Severity::Warn), 1);
+    assert_eq!(report.count(Severity::Error), 0);
+}
+
+#[test]
+fn fix_keeps_directives() {
+    let dir = tempfile::tempdir().unwrap();
+    let folders = ["de", "en"].map(|locale| dir.path().join(locale));
+    std::fs::create_dir(&folders[0]).unwrap();
+    std::fs::create_dir(&folders[1]).unwrap();
+    std::fs::write(
+        folders[0].join("common.json"),
+        "{\"saveButton\": \"Speichern\", \"//jsoncpl-disable\": \"missing-key\", \"legacy\": \"Alt\"}",
+    ).unwrap();
+    std::fs::write(folders[1].join("common.json"), "{\"saveButton\": \"Save\"}").unwrap();
+    let naming = KeyNaming {
+        objects: None,
+        leaves: Some(KeyCase::Snake),
+    };
+
+    assert!(fix(&STYLE, &naming, read_folders(&folders)).is_empty());
+    assert_eq!(
+        std::fs::read_to_string(folders[0].join("common.json")).unwrap(),
+        "{\n    \"//jsoncpl-disable\": \"missing-key\",\n    \"legacy\": \"Alt\",\n    \"save_button\": \"Speichern\"\n}",
+    );
+    let options = LintOptions {
+        naming,
+        ..LintOptions::default()
+    };
+    assert!(lint(&STYLE, read_folders(&folders), &options).is_empty());
+}
+
+#[test]
+fn fix_renames_keys_in_every_folder() {
+    let dir = tempfile::tempdir().unwrap();
+    let folders = ["de", "en"].map(|locale| dir.path().join(locale));
+    let contents = [
+        "{\"saveButton\":\"Speichern\",\"cancel_button\":\"X\",\"cancelButton\":\"Abbrechen\"}",
+        "{\"saveButton\":\"Save\",\"cancelButton\":\"Cancel\"}",
+    ];
+    for (folder, content) in folders.iter().zip(contents) {
+        std::fs::create_dir(folder).unwrap();
+        std::fs::write(folder.join("common.json"), content).unwrap();
+        std::fs::write(folder.join("main.json"), "{\"title\":\"Title\"}").unwrap();
+    }
+    let naming = KeyNaming {
+        objects: None,
+        leaves: Some(KeyCase::Snake),
+    };
+
+    assert!(fix(&STYLE, &naming, read_folders(&folders)).is_empty());
+    // cancelButton would collide in de, so it is kept in en as well
+    assert_eq!(
+        std::fs::read_to_string(folders[1].join("common.json")).unwrap(),
+        "{\n    \"cancelButton\": \"Cancel\",\n    \"save_button\": \"Save\"\n}",
+    );
+    // Files without renamed keys are not written
+    assert_eq!(std::fs::read_to_string(folders[1].join("main.json")).unwrap(), "{\"title\":\"Title\"}");
+}